@@ -0,0 +1,226 @@
+//! Derive macros for the [apache_age](https://docs.rs/apache_age) crate.
+//!
+//! Use them through the `derive` feature of the `apache_age` crate
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr};
+
+/// Implements `apache_age::FromAgRow` for the struct.
+///
+/// Named fields are read from the columns with the same name (`#[age(rename = "...")]`
+/// overrides it), tuple struct fields are read by their position
+#[proc_macro_derive(FromAgRow, attributes(age))]
+pub fn derive_from_ag_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match from_ag_row(input) {
+        Ok(x) => x.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn from_ag_row(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "FromAgRow can be derived only for structs",
+            ))
+        }
+    };
+
+    let body = match fields {
+        Fields::Named(fields) => {
+            let fields = fields
+                .named
+                .iter()
+                .map(|field| {
+                    let ident = field.ident.as_ref().expect("named field");
+                    let column = match column_rename(field)? {
+                        Some(column) => column.value(),
+                        None => ident.to_string().trim_start_matches("r#").to_string(),
+                    };
+                    Ok(quote! { #ident: row.try_get(#column)? })
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            quote! { Self { #(#fields),* } }
+        }
+        Fields::Unnamed(fields) => {
+            for field in &fields.unnamed {
+                if let Some(rename) = column_rename(field)? {
+                    return Err(syn::Error::new_spanned(
+                        rename,
+                        "Tuple struct fields are read by their position",
+                    ));
+                }
+            }
+            let fields = (0..fields.unnamed.len()).map(|i| quote! { row.try_get(#i)? });
+            quote! { Self(#(#fields),*) }
+        }
+        Fields::Unit => quote! { Self },
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::apache_age::FromAgRow for #name #ty_generics #where_clause {
+            fn from_ag_row(
+                row: &::apache_age::Row,
+            ) -> ::std::result::Result<Self, ::apache_age::Error> {
+                ::std::result::Result::Ok(#body)
+            }
+        }
+    })
+}
+
+/// Implements `apache_age::Label` for the struct.
+///
+/// Label defaults to the struct name (`#[age_label(label = "...")]` overrides it) and is a vertex
+/// label unless `#[age_label(edge)]` is set. Fields marked with `#[age_label(required)]`
+/// or `#[age_label(unique)]` are listed as required or unique properties, named after the field
+/// (`#[age_label(rename = "...")]` overrides it). The own attribute keeps it apart from
+/// the `#[age(...)]` of `FromAgRow`, so both can be derived for the same struct
+#[proc_macro_derive(AgeLabel, attributes(age_label))]
+pub fn derive_age_label(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match age_label(input) {
+        Ok(x) => x.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn age_label(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "AgeLabel can be derived only for structs",
+            ))
+        }
+    };
+
+    let mut label = None;
+    let mut edge = false;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|x| x.path().is_ident("age_label"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("label") {
+                let value = meta.value()?.parse::<LitStr>()?;
+                if !is_label_name(&value.value()) {
+                    return Err(syn::Error::new_spanned(value, "Invalid label name"));
+                }
+                label = Some(value.value());
+                Ok(())
+            } else if meta.path.is_ident("edge") {
+                edge = true;
+                Ok(())
+            } else if meta.path.is_ident("vertex") {
+                edge = false;
+                Ok(())
+            } else {
+                Err(meta.error("Unsupported age_label attribute"))
+            }
+        })?;
+    }
+    let label = label.unwrap_or_else(|| name.to_string().trim_start_matches("r#").to_string());
+    let kind = if edge {
+        quote! { ::apache_age::LabelKind::Edge }
+    } else {
+        quote! { ::apache_age::LabelKind::Vertex }
+    };
+
+    let mut required = vec![];
+    let mut unique = vec![];
+    if let Fields::Unnamed(fields) = fields {
+        if let Some(attr) = fields
+            .unnamed
+            .iter()
+            .flat_map(|x| &x.attrs)
+            .find(|x| x.path().is_ident("age_label"))
+        {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "Tuple struct fields are not bound to the properties",
+            ));
+        }
+    }
+    if let Fields::Named(fields) = fields {
+        for field in &fields.named {
+            let ident = field.ident.as_ref().expect("named field");
+            let mut property = ident.to_string().trim_start_matches("r#").to_string();
+            let (mut is_required, mut is_unique) = (false, false);
+
+            for attr in field
+                .attrs
+                .iter()
+                .filter(|x| x.path().is_ident("age_label"))
+            {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("required") {
+                        is_required = true;
+                        Ok(())
+                    } else if meta.path.is_ident("unique") {
+                        is_unique = true;
+                        Ok(())
+                    } else if meta.path.is_ident("rename") {
+                        property = meta.value()?.parse::<LitStr>()?.value();
+                        Ok(())
+                    } else {
+                        Err(meta.error("Unsupported age_label attribute"))
+                    }
+                })?;
+            }
+
+            if is_required {
+                required.push(property.clone());
+            }
+            if is_unique {
+                unique.push(property);
+            }
+        }
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::apache_age::Label for #name #ty_generics #where_clause {
+            const LABEL: &'static str = #label;
+            const KIND: ::apache_age::LabelKind = #kind;
+            const REQUIRED: &'static [&'static str] = &[#(#required),*];
+            const UNIQUE: &'static [&'static str] = &[#(#unique),*];
+        }
+    })
+}
+
+/// Same rules as `apache_age::LabelName` uses
+fn is_label_name(name: &str) -> bool {
+    (1..=63).contains(&name.len())
+        && name.starts_with(|c: char| c.is_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+fn column_rename(field: &syn::Field) -> syn::Result<Option<LitStr>> {
+    let mut rename = None;
+
+    for attr in field.attrs.iter().filter(|x| x.path().is_ident("age")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                rename = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else {
+                Err(meta.error("Unsupported age attribute"))
+            }
+        })?;
+    }
+
+    Ok(rename)
+}
@@ -0,0 +1,22 @@
+#![no_main]
+
+use apache_age::{AgType, AgValue, Edge, Path, Vertex};
+use libfuzzer_sys::fuzz_target;
+use postgres_types::{FromSql, Kind, Type};
+use serde_json::Value;
+
+fuzz_target!(|data: &[u8]| {
+    let ty = Type::new("agtype".into(), 0, Kind::Simple, "ag_catalog".into());
+    let raw = [&[1u8], data].concat();
+
+    if let Ok(value) = AgValue::from_sql(&ty, &raw) {
+        // Whatever was parsed has to survive the round trip
+        let text = value.to_string();
+        assert_eq!(text.parse::<AgValue>().unwrap().to_string(), text);
+    }
+
+    let _ = Vertex::<Value>::from_sql(&ty, &raw);
+    let _ = Edge::<Value>::from_sql(&ty, &raw);
+    let _ = Path::<Value, Value>::from_sql(&ty, &raw);
+    let _ = AgType::<Value>::from_sql(&ty, &raw);
+});
@@ -1,18 +1,25 @@
+use crate::agtype::{decode_column, from_slice, from_slice_annotated};
+use crate::error::Error;
+use crate::graph_id::GraphId;
 use bytes::BufMut;
 use postgres_types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
-use serde::{Deserialize, Serialize};
-use std::io::Read;
+use serde::de::{self, MapAccess, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::io::Write;
+use std::marker::PhantomData;
 
 /// Represents vertex within graph. Used during process of vertex deserialization
-#[derive(Debug, Serialize, Deserialize)]
+///
+/// Unknown fields are skipped, but maps with `start_id` or `end_id` (edges) are rejected
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct Vertex<T> {
-    id: u64,
-    label: String,
-    properties: T,
+    pub(crate) id: GraphId,
+    pub(crate) label: String,
+    pub(crate) properties: T,
 }
 
 impl<T> Vertex<T> {
-    pub fn id(&self) -> u64 {
+    pub fn id(&self) -> GraphId {
         self.id
     }
 
@@ -25,18 +32,81 @@ impl<T> Vertex<T> {
     }
 }
 
+struct VertexVisitor<T>(PhantomData<T>);
+
+impl<'de, T> Visitor<'de> for VertexVisitor<T>
+where
+    T: Deserialize<'de>,
+{
+    type Value = Vertex<T>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a vertex")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut id = None;
+        let mut label = None;
+        let mut properties = None;
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "id" if id.is_some() => return Err(de::Error::duplicate_field("id")),
+                "id" => id = Some(map.next_value()?),
+                "label" if label.is_some() => return Err(de::Error::duplicate_field("label")),
+                "label" => label = Some(map.next_value()?),
+                "properties" if properties.is_some() => {
+                    return Err(de::Error::duplicate_field("properties"))
+                }
+                "properties" => properties = Some(map.next_value()?),
+                "start_id" | "end_id" => {
+                    return Err(de::Error::custom("Edge cannot be decoded as a vertex"))
+                }
+                _ => {
+                    map.next_value::<de::IgnoredAny>()?;
+                }
+            }
+        }
+
+        Ok(Vertex {
+            id: id.ok_or_else(|| de::Error::missing_field("id"))?,
+            label: label.ok_or_else(|| de::Error::missing_field("label"))?,
+            properties: properties.ok_or_else(|| de::Error::missing_field("properties"))?,
+        })
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Vertex<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            "Vertex",
+            &["id", "label", "properties"],
+            VertexVisitor(PhantomData),
+        )
+    }
+}
+
 /// Represents edge within graph. Used during process of edge deserialization
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Edge<T> {
-    id: u64,
-    label: String,
-    properties: T,
-    start_id: u64,
-    end_id: u64,
+    pub(crate) id: GraphId,
+    pub(crate) label: String,
+    pub(crate) properties: T,
+    pub(crate) start_id: GraphId,
+    pub(crate) end_id: GraphId,
 }
 
 impl<T> Edge<T> {
-    pub fn id(&self) -> u64 {
+    pub fn id(&self) -> GraphId {
         self.id
     }
 
@@ -48,19 +118,23 @@ impl<T> Edge<T> {
         &self.properties
     }
 
-    pub fn start_id(&self) -> u64 {
+    pub fn start_id(&self) -> GraphId {
         self.start_id
     }
 
-    pub fn end_id(&self) -> u64 {
+    pub fn end_id(&self) -> GraphId {
         self.end_id
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Represents path within graph. Used during process of path deserialization
+///
+/// Can be decoded both from the agtype path (alternating vertices and edges)
+/// and from the map with `vertices` and `edges` keys
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct Path<V, E> {
-    vertices: Vec<Vertex<V>>,
-    edges: Vec<Edge<E>>,
+    pub(crate) vertices: Vec<Vertex<V>>,
+    pub(crate) edges: Vec<Edge<E>>,
 }
 
 impl<V, E> Path<V, E> {
@@ -73,38 +147,88 @@ impl<V, E> Path<V, E> {
     }
 }
 
-const VERTEX_SUFFIX: &[u8] = "::vertex".as_bytes();
-const EDGE_SUFFIX: &[u8] = "::edge".as_bytes();
-const PATH_SUFFIX: &[u8] = "::path".as_bytes();
+struct PathVisitor<V, E>(PhantomData<(V, E)>);
 
-const VERTEX_SUFFIX_LEN: usize = 8;
-const EDGE_SUFFIX_LEN: usize = 6;
-const PATH_SUFFIX_LEN: usize = 6;
-
-impl<'a, T> FromSql<'a> for Vertex<T>
+impl<'de, V, E> Visitor<'de> for PathVisitor<V, E>
 where
-    T: Deserialize<'a>,
+    V: Deserialize<'de>,
+    E: Deserialize<'de>,
 {
-    fn from_sql(
-        ty: &Type,
-        mut raw: &'a [u8],
-    ) -> Result<Vertex<T>, Box<dyn std::error::Error + Sync + Send>> {
-        if ty.schema() != "ag_catalog" || ty.name() != "agtype" {
-            return Err("Only ag_catalog.agtype is supported".into());
+    type Value = Path<V, E>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a path")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut vertices = vec![];
+        let mut edges = vec![];
+
+        loop {
+            match seq.next_element::<Vertex<V>>()? {
+                Some(vertex) => vertices.push(vertex),
+                None => break,
+            }
+            match seq.next_element::<Edge<E>>()? {
+                Some(edge) => edges.push(edge),
+                None => break,
+            }
         }
 
-        let mut b = [0; 1];
-        raw.read_exact(&mut b)?;
+        if vertices.len() != edges.len() + 1 {
+            return Err(de::Error::custom("Path has to end with a vertex"));
+        }
 
-        // We only support version 1 of the jsonb binary format
-        if b[0] != 1 {
-            return Err("unsupported JSONB encoding version".into());
+        Ok(Path { vertices, edges })
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut vertices = None;
+        let mut edges = None;
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "vertices" => vertices = Some(map.next_value()?),
+                "edges" => edges = Some(map.next_value()?),
+                _ => return Err(de::Error::unknown_field(&key, &["vertices", "edges"])),
+            }
         }
 
-        // Remove ::vertex from bytes
-        let raw_splitted = raw.split_at(raw.len() - VERTEX_SUFFIX_LEN).0;
+        Ok(Path {
+            vertices: vertices.ok_or_else(|| de::Error::missing_field("vertices"))?,
+            edges: edges.ok_or_else(|| de::Error::missing_field("edges"))?,
+        })
+    }
+}
 
-        serde_json::de::from_slice::<Vertex<T>>(raw_splitted).map_err(Into::into)
+impl<'de, V, E> Deserialize<'de> for Path<V, E>
+where
+    V: Deserialize<'de>,
+    E: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(PathVisitor(PhantomData))
+    }
+}
+
+impl<'a, T> FromSql<'a> for Vertex<T>
+where
+    T: Deserialize<'a>,
+{
+    fn from_sql(
+        ty: &Type,
+        raw: &'a [u8],
+    ) -> Result<Vertex<T>, Box<dyn std::error::Error + Sync + Send>> {
+        decode_column(ty, raw, |x| from_slice_annotated(x, "vertex"))
     }
 
     fn accepts(ty: &Type) -> bool {
@@ -118,24 +242,9 @@ where
 {
     fn from_sql(
         ty: &Type,
-        mut raw: &'a [u8],
+        raw: &'a [u8],
     ) -> Result<Edge<T>, Box<dyn std::error::Error + Sync + Send>> {
-        if ty.schema() != "ag_catalog" || ty.name() != "agtype" {
-            return Err("Only ag_catalog.agtype is supported".into());
-        }
-
-        let mut b = [0; 1];
-        raw.read_exact(&mut b)?;
-
-        // We only support version 1 of the jsonb binary format
-        if b[0] != 1 {
-            return Err("unsupported JSONB encoding version".into());
-        }
-
-        // Remove ::edge from bytes
-        let raw_splitted = raw.split_at(raw.len() - EDGE_SUFFIX_LEN).0;
-
-        serde_json::de::from_slice::<Edge<T>>(raw_splitted).map_err(Into::into)
+        decode_column(ty, raw, |x| from_slice_annotated(x, "edge"))
     }
 
     fn accepts(ty: &Type) -> bool {
@@ -143,7 +252,6 @@ where
     }
 }
 
-/// Represents path in graph. Used during process of path deserialization
 impl<'a, V, E> FromSql<'a> for Path<V, E>
 where
     V: Deserialize<'a>,
@@ -151,59 +259,143 @@ where
 {
     fn from_sql(
         ty: &Type,
-        mut raw: &'a [u8],
+        raw: &'a [u8],
     ) -> Result<Path<V, E>, Box<dyn std::error::Error + Sync + Send>> {
-        if ty.schema() != "ag_catalog" || ty.name() != "agtype" {
-            return Err("Only ag_catalog.agtype is supported".into());
-        }
+        decode_column(ty, raw, |x| from_slice_annotated(x, "path"))
+    }
 
-        let mut b = [0; 1];
-        raw.read_exact(&mut b)?;
+    fn accepts(ty: &Type) -> bool {
+        ty.schema() == "ag_catalog" && ty.name() == "agtype"
+    }
+}
 
-        // We only support version 1 of the jsonb binary format
-        if b[0] != 1 {
-            return Err("unsupported JSONB encoding version".into());
-        }
+impl<T: Serialize> Vertex<T> {
+    fn write_agtype(&self, out: &mut impl Write) -> Result<(), serde_json::Error> {
+        write!(out, "{{\"id\": {}, \"label\": ", self.id).map_err(serde_json::Error::io)?;
+        serde_json::to_writer(&mut *out, &self.label)?;
+        out.write_all(b", \"properties\": ")
+            .map_err(serde_json::Error::io)?;
+        serde_json::to_writer(&mut *out, &self.properties)?;
+        out.write_all(b"}::vertex").map_err(serde_json::Error::io)
+    }
+}
 
-        if !(raw[0] == "[".as_bytes()[0] && &raw[raw.len() - PATH_SUFFIX_LEN..] == PATH_SUFFIX) {
-            return Err("Invalid path definition".into());
-        }
+impl<T: Serialize> Edge<T> {
+    fn write_agtype(&self, out: &mut impl Write) -> Result<(), serde_json::Error> {
+        write!(out, "{{\"id\": {}, \"label\": ", self.id).map_err(serde_json::Error::io)?;
+        serde_json::to_writer(&mut *out, &self.label)?;
+        write!(
+            out,
+            ", \"end_id\": {}, \"start_id\": {}, \"properties\": ",
+            self.end_id, self.start_id
+        )
+        .map_err(serde_json::Error::io)?;
+        serde_json::to_writer(&mut *out, &self.properties)?;
+        out.write_all(b"}::edge").map_err(serde_json::Error::io)
+    }
+}
 
-        let mut vertices: Vec<Vertex<V>> = vec![];
-        let mut edges: Vec<Edge<E>> = vec![];
-
-        let mut first_open_bracket = raw.len();
-
-        for (i, character) in raw[..raw.len() - PATH_SUFFIX_LEN - 1].iter().enumerate() {
-            if *character as char == '{' && first_open_bracket == raw.len() {
-                first_open_bracket = i;
-            } else if &raw[i..i + VERTEX_SUFFIX_LEN] == VERTEX_SUFFIX {
-                match serde_json::de::from_slice::<Vertex<V>>(&raw[first_open_bracket..i]) {
-                    Ok(vertex) => {
-                        vertices.push(vertex);
-                        first_open_bracket = raw.len();
-                    }
-                    Err(e) => return Err(e.into()),
-                };
-            } else if &raw[i..i + EDGE_SUFFIX_LEN] == EDGE_SUFFIX {
-                match serde_json::de::from_slice::<Edge<E>>(&raw[first_open_bracket..i]) {
-                    Ok(edge) => {
-                        edges.push(edge);
-                        first_open_bracket = raw.len();
-                    }
-                    Err(e) => return Err(e.into()),
-                };
-            }
-        }
-        Ok(Path { vertices, edges })
+/// Vertex is sent as an agtype annotated with `::vertex`
+impl<T> ToSql for Vertex<T>
+where
+    T: Serialize,
+    T: std::fmt::Debug,
+{
+    fn accepts(ty: &Type) -> bool {
+        ty.schema() == "ag_catalog" && ty.name() == "agtype"
+    }
+
+    fn to_sql(
+        &self,
+        _ty: &Type,
+        out: &mut postgres_types::private::BytesMut,
+    ) -> Result<postgres_types::IsNull, Box<dyn std::error::Error + Sync + Send>>
+    where
+        Self: Sized,
+    {
+        out.put_u8(1);
+        self.write_agtype(&mut out.writer())?;
+        Ok(IsNull::No)
     }
 
+    to_sql_checked!();
+}
+
+/// Edge is sent as an agtype annotated with `::edge`
+impl<T> ToSql for Edge<T>
+where
+    T: Serialize,
+    T: std::fmt::Debug,
+{
     fn accepts(ty: &Type) -> bool {
         ty.schema() == "ag_catalog" && ty.name() == "agtype"
     }
+
+    fn to_sql(
+        &self,
+        _ty: &Type,
+        out: &mut postgres_types::private::BytesMut,
+    ) -> Result<postgres_types::IsNull, Box<dyn std::error::Error + Sync + Send>>
+    where
+        Self: Sized,
+    {
+        out.put_u8(1);
+        self.write_agtype(&mut out.writer())?;
+        Ok(IsNull::No)
+    }
+
+    to_sql_checked!();
+}
+
+/// Path is sent as an agtype list of vertices and edges annotated with `::path`
+impl<V, E> ToSql for Path<V, E>
+where
+    V: Serialize,
+    V: std::fmt::Debug,
+    E: Serialize,
+    E: std::fmt::Debug,
+{
+    fn accepts(ty: &Type) -> bool {
+        ty.schema() == "ag_catalog" && ty.name() == "agtype"
+    }
+
+    fn to_sql(
+        &self,
+        _ty: &Type,
+        out: &mut postgres_types::private::BytesMut,
+    ) -> Result<postgres_types::IsNull, Box<dyn std::error::Error + Sync + Send>>
+    where
+        Self: Sized,
+    {
+        if self.vertices.len() != self.edges.len() + 1 {
+            return Err(Error::Serialization(
+                "Path has to contain one vertex more than edges".to_string(),
+            )
+            .into());
+        }
+
+        out.put_u8(1);
+        let mut writer = out.writer();
+        writer.write_all(b"[")?;
+        for (i, vertex) in self.vertices.iter().enumerate() {
+            if i > 0 {
+                writer.write_all(b", ")?;
+                self.edges[i - 1].write_agtype(&mut writer)?;
+                writer.write_all(b", ")?;
+            }
+            vertex.write_agtype(&mut writer)?;
+        }
+        writer.write_all(b"]::path")?;
+        Ok(IsNull::No)
+    }
+
+    to_sql_checked!();
 }
 
 /// Simple wrapper (similar to JSONB) that handles agtype serialization and deserialization
+///
+/// Vertices, edges and paths can be decoded at any depth of the wrapped value,
+/// e.g. as `AgType<Vec<Vertex<T>>>` for the `RETURN collect(n)`
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AgType<T>(pub T);
 
@@ -238,21 +430,9 @@ where
 {
     fn from_sql(
         ty: &Type,
-        mut raw: &'a [u8],
+        raw: &'a [u8],
     ) -> Result<AgType<T>, Box<dyn std::error::Error + Sync + Send>> {
-        if ty.schema() != "ag_catalog" || ty.name() != "agtype" {
-            return Err("Only ag_catalog.agtype is supported".into());
-        }
-
-        let mut b = [0; 1];
-        raw.read_exact(&mut b)?;
-
-        // We only support version 1 of the jsonb binary format
-        if b[0] != 1 {
-            return Err("unsupported JSONB encoding version".into());
-        }
-
-        serde_json::de::from_slice::<AgType<T>>(raw).map_err(Into::into)
+        decode_column(ty, raw, from_slice)
     }
 
     fn accepts(ty: &Type) -> bool {
@@ -0,0 +1,461 @@
+use super::lexer::{Lexer, Token};
+use super::DecodeError;
+use serde::de::{self, DeserializeSeed, IntoDeserializer, Visitor};
+use serde::forward_to_deserialize_any;
+use std::borrow::Cow;
+
+/// Maximum nesting of lists and maps, protects stack from malicious inputs
+const MAX_DEPTH: usize = 128;
+
+/// Deserializes agtype text directly into the requested type.
+///
+/// Type annotations are understood natively: vertices and edges are presented as maps,
+/// paths as lists of alternating vertices and edges. Numerics are presented as strings,
+/// unless a number is explicitly requested.
+///
+/// Strings without escape sequences are borrowed from the input, so `&'de str` can be used.
+pub struct Deserializer<'de> {
+    lexer: Lexer<'de>,
+    depth: usize,
+    root_annotation: Option<&'de str>,
+}
+
+impl<'de> Deserializer<'de> {
+    pub fn from_slice(raw: &'de [u8]) -> Self {
+        Self {
+            lexer: Lexer::new(raw),
+            depth: 0,
+            root_annotation: None,
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &'de str) -> Self {
+        Self::from_slice(s.as_bytes())
+    }
+
+    /// Ensures that the whole input was consumed
+    pub fn end(&mut self) -> Result<(), DecodeError> {
+        self.lexer.end()
+    }
+
+    /// Annotation of the top level value (if any), e.g. `vertex`
+    pub(crate) fn root_annotation(&self) -> Option<&'de str> {
+        self.root_annotation
+    }
+
+    fn error(&mut self, message: impl Into<String>) -> DecodeError {
+        DecodeError::new(self.lexer.offset(), message)
+    }
+
+    fn take_annotation(&mut self) -> Result<Option<(usize, &'de str)>, DecodeError> {
+        if let Some(Token::Annotation(_)) = self.lexer.peek()? {
+            if let Some((offset, Token::Annotation(x))) = self.lexer.next_token()? {
+                if self.depth == 0 {
+                    self.root_annotation = Some(x);
+                }
+                return Ok(Some((offset, x)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Consumes annotation of the non numeric value, verifying it is allowed
+    fn annotation(&mut self, allowed: &[&str]) -> Result<(), DecodeError> {
+        match self.take_annotation()? {
+            Some((offset, x)) if !allowed.contains(&x) => Err(DecodeError::new(
+                offset,
+                format!("Invalid value annotated with ::{}", x),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn enter(&mut self, offset: usize) -> Result<(), DecodeError> {
+        if self.depth == MAX_DEPTH {
+            return Err(DecodeError::new(offset, "Recursion limit exceeded"));
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    fn expect(&mut self, expected: Token<'de>) -> Result<(), DecodeError> {
+        let (offset, token) = self.lexer.expect_token()?;
+        if token != expected {
+            return Err(DecodeError::new(
+                offset,
+                format!(
+                    "Expected {}, found {}",
+                    expected.describe(),
+                    token.describe()
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Classifies the number using its annotation (if any)
+    fn number_text(&mut self, text: &'de str) -> Result<Number<'de>, DecodeError> {
+        let is_float = !text.bytes().all(|c| c == b'-' || c.is_ascii_digit());
+
+        match self.take_annotation()? {
+            Some((_, "numeric")) => Ok(Number::Numeric(text)),
+            Some((_, "float")) => Ok(Number::Float(text)),
+            Some((_, "integer")) | None if !is_float => Ok(Number::Integer(text)),
+            None => Ok(Number::Float(text)),
+            Some((offset, x)) => Err(DecodeError::new(
+                offset,
+                format!("Number cannot be annotated with ::{}", x),
+            )),
+        }
+    }
+
+    fn visit_number<V>(
+        &mut self,
+        offset: usize,
+        number: Number<'de>,
+        visitor: V,
+    ) -> Result<V::Value, DecodeError>
+    where
+        V: Visitor<'de>,
+    {
+        let text = number.text();
+        let result = if let (Number::Integer(_) | Number::Numeric(_), Ok(x)) =
+            (&number, text.parse::<i64>())
+        {
+            visitor.visit_i64(x)
+        } else if let (Number::Integer(_) | Number::Numeric(_), Ok(x)) =
+            (&number, text.parse::<u64>())
+        {
+            visitor.visit_u64(x)
+        } else if let (Number::Float(_) | Number::Numeric(_), Ok(x)) =
+            (&number, text.parse::<f64>())
+        {
+            visitor.visit_f64(x)
+        } else {
+            return Err(DecodeError::new(offset, format!("Invalid number {}", text)));
+        };
+
+        result.map_err(|e: DecodeError| e.or_offset(offset))
+    }
+
+    fn deserialize_number<V>(&mut self, visitor: V) -> Result<V::Value, DecodeError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.lexer.peek()? {
+            Some(Token::Number(_)) => {}
+            _ => return de::Deserializer::deserialize_any(self, visitor),
+        }
+
+        match self.lexer.expect_token()? {
+            (offset, Token::Number(text)) => {
+                let number = self.number_text(text)?;
+                self.visit_number(offset, number, visitor)
+            }
+            (offset, _) => Err(DecodeError::new(offset, "Expected number")),
+        }
+    }
+}
+
+enum Number<'de> {
+    Integer(&'de str),
+    Float(&'de str),
+    Numeric(&'de str),
+}
+
+impl<'de> Number<'de> {
+    fn text(&self) -> &'de str {
+        match self {
+            Number::Integer(x) | Number::Float(x) | Number::Numeric(x) => x,
+        }
+    }
+}
+
+/// Deserializes value out of the agtype text (without the binary header)
+pub fn from_slice<'a, T>(raw: &'a [u8]) -> Result<T, DecodeError>
+where
+    T: de::Deserialize<'a>,
+{
+    let mut deserializer = Deserializer::from_slice(raw);
+    let value =
+        T::deserialize(&mut deserializer).map_err(|e| e.or_offset(deserializer.lexer.offset()))?;
+    deserializer.end()?;
+    Ok(value)
+}
+
+/// Deserializes value out of the agtype text
+pub fn from_str<'a, T>(s: &'a str) -> Result<T, DecodeError>
+where
+    T: de::Deserialize<'a>,
+{
+    from_slice(s.as_bytes())
+}
+
+/// Deserializes value, requiring the top level value to be annotated (e.g. with `::vertex`)
+pub(crate) fn from_slice_annotated<'a, T>(raw: &'a [u8], annotation: &str) -> Result<T, DecodeError>
+where
+    T: de::Deserialize<'a>,
+{
+    let mut deserializer = Deserializer::from_slice(raw);
+    let value =
+        T::deserialize(&mut deserializer).map_err(|e| e.or_offset(deserializer.lexer.offset()))?;
+    deserializer.end()?;
+
+    if deserializer.root_annotation() != Some(annotation) {
+        return Err(DecodeError::new(0, format!("Expected ::{}", annotation)));
+    }
+
+    Ok(value)
+}
+
+macro_rules! deserialize_number {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                self.deserialize_number(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
+    type Error = DecodeError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let (offset, token) = self.lexer.expect_token()?;
+
+        // Errors raised by the visitor point at the beginning of the value
+        let result = match token {
+            Token::Null => {
+                self.annotation(&[])?;
+                visitor.visit_unit()
+            }
+            Token::Bool(x) => {
+                self.annotation(&[])?;
+                visitor.visit_bool(x)
+            }
+            Token::Number(text) => match self.number_text(text)? {
+                Number::Numeric(x) => visitor.visit_borrowed_str(x),
+                number => self.visit_number(offset, number, visitor),
+            },
+            Token::String(x) => {
+                self.annotation(&[])?;
+                match x {
+                    Cow::Borrowed(x) => visitor.visit_borrowed_str(x),
+                    Cow::Owned(x) => visitor.visit_string(x),
+                }
+            }
+            Token::BeginMap => {
+                self.enter(offset)?;
+                let value = visitor.visit_map(MapAccess {
+                    de: &mut *self,
+                    first: true,
+                })?;
+                self.expect(Token::EndMap)?;
+                self.depth -= 1;
+                self.annotation(&["vertex", "edge"])?;
+                Ok(value)
+            }
+            Token::BeginList => {
+                self.enter(offset)?;
+                let value = visitor.visit_seq(SeqAccess {
+                    de: &mut *self,
+                    first: true,
+                })?;
+                self.expect(Token::EndList)?;
+                self.depth -= 1;
+                self.annotation(&["path"])?;
+                Ok(value)
+            }
+            token => Err(DecodeError::new(
+                offset,
+                format!("Expected value, found {}", token.describe()),
+            )),
+        };
+
+        result.map_err(|e| e.or_offset(offset))
+    }
+
+    deserialize_number! {
+        deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64 deserialize_i128
+        deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64 deserialize_u128
+        deserialize_f32 deserialize_f64
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if self.lexer.peek()? == Some(&Token::Null) {
+            self.lexer.next_token()?;
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.lexer.expect_token()? {
+            (offset, Token::String(x)) => visitor
+                .visit_enum(x.into_owned().into_deserializer())
+                .map_err(|e: DecodeError| e.or_offset(offset)),
+            (offset, Token::BeginMap) => {
+                self.enter(offset)?;
+                let value = visitor.visit_enum(EnumAccess { de: &mut *self })?;
+                self.expect(Token::EndMap)?;
+                self.depth -= 1;
+                Ok(value)
+            }
+            (offset, _) => Err(DecodeError::new(
+                offset,
+                "Expected string or map with a single key for an enum",
+            )),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool char str string bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct MapAccess<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+    first: bool,
+}
+
+impl<'de, 'a> de::MapAccess<'de> for MapAccess<'a, 'de> {
+    type Error = DecodeError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        if self.de.lexer.peek()? == Some(&Token::EndMap) {
+            return Ok(None);
+        }
+
+        if !self.first {
+            self.de.expect(Token::Comma)?;
+        }
+        self.first = false;
+
+        match self.de.lexer.peek()? {
+            Some(Token::String(_)) => seed.deserialize(&mut *self.de).map(Some),
+            _ => Err(self.de.error("Expected key")),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        self.de.expect(Token::Colon)?;
+        seed.deserialize(&mut *self.de)
+    }
+}
+
+struct SeqAccess<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+    first: bool,
+}
+
+impl<'de, 'a> de::SeqAccess<'de> for SeqAccess<'a, 'de> {
+    type Error = DecodeError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        if self.de.lexer.peek()? == Some(&Token::EndList) {
+            return Ok(None);
+        }
+
+        if !self.first {
+            self.de.expect(Token::Comma)?;
+        }
+        self.first = false;
+
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+}
+
+struct EnumAccess<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+}
+
+impl<'de, 'a> de::EnumAccess<'de> for EnumAccess<'a, 'de> {
+    type Error = DecodeError;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let variant = match self.de.lexer.peek()? {
+            Some(Token::String(_)) => seed.deserialize(&mut *self.de)?,
+            _ => return Err(self.de.error("Expected enum variant")),
+        };
+        self.de.expect(Token::Colon)?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de, 'a> de::VariantAccess<'de> for EnumAccess<'a, 'de> {
+    type Error = DecodeError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        de::Deserialize::deserialize(self.de)
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.de)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_seq(self.de, visitor)
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_map(self.de, visitor)
+    }
+}
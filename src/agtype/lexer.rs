@@ -0,0 +1,317 @@
+use super::DecodeError;
+use std::borrow::Cow;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Token<'a> {
+    BeginMap,
+    EndMap,
+    BeginList,
+    EndList,
+    Colon,
+    Comma,
+    Null,
+    Bool(bool),
+    /// Textual form of the number, validated against the JSON grammar.
+    /// Can be also one of the special float literals: `NaN`, `Infinity`, `-Infinity`
+    Number(&'a str),
+    String(Cow<'a, str>),
+    /// Name of the type annotation, without leading `::`
+    Annotation(&'a str),
+}
+
+impl<'a> Token<'a> {
+    pub(crate) fn describe(&self) -> &'static str {
+        match self {
+            Token::BeginMap => "'{'",
+            Token::EndMap => "'}'",
+            Token::BeginList => "'['",
+            Token::EndList => "']'",
+            Token::Colon => "':'",
+            Token::Comma => "','",
+            Token::Null => "null",
+            Token::Bool(_) => "boolean",
+            Token::Number(_) => "number",
+            Token::String(_) => "string",
+            Token::Annotation(_) => "annotation",
+        }
+    }
+}
+
+/// Splits agtype text into tokens. Every token is returned with its byte offset
+pub(crate) struct Lexer<'a> {
+    raw: &'a [u8],
+    pos: usize,
+    peeked: Option<(usize, Token<'a>)>,
+}
+
+impl<'a> Lexer<'a> {
+    pub(crate) fn new(raw: &'a [u8]) -> Self {
+        Self {
+            raw,
+            pos: 0,
+            peeked: None,
+        }
+    }
+
+    /// Offset of the next token (or end of the input)
+    pub(crate) fn offset(&mut self) -> usize {
+        match &self.peeked {
+            Some((offset, _)) => *offset,
+            None => {
+                self.skip_whitespace();
+                self.pos
+            }
+        }
+    }
+
+    pub(crate) fn peek(&mut self) -> Result<Option<&Token<'a>>, DecodeError> {
+        if self.peeked.is_none() {
+            self.peeked = self.lex()?;
+        }
+        Ok(self.peeked.as_ref().map(|(_, token)| token))
+    }
+
+    pub(crate) fn next_token(&mut self) -> Result<Option<(usize, Token<'a>)>, DecodeError> {
+        match self.peeked.take() {
+            Some(x) => Ok(Some(x)),
+            None => self.lex(),
+        }
+    }
+
+    /// Returns next token, treating end of the input as an error
+    pub(crate) fn expect_token(&mut self) -> Result<(usize, Token<'a>), DecodeError> {
+        let offset = self.offset();
+        self.next_token()?
+            .ok_or_else(|| DecodeError::new(offset, "Unexpected end of input"))
+    }
+
+    /// Ensures there is nothing left except whitespaces
+    pub(crate) fn end(&mut self) -> Result<(), DecodeError> {
+        match self.next_token()? {
+            None => Ok(()),
+            Some((offset, _)) => Err(DecodeError::new(
+                offset,
+                "Trailing characters after agtype value",
+            )),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.raw.get(self.pos), Some(b' ' | b'\n' | b'\r' | b'\t')) {
+            self.pos += 1;
+        }
+    }
+
+    fn lex(&mut self) -> Result<Option<(usize, Token<'a>)>, DecodeError> {
+        self.skip_whitespace();
+        let start = self.pos;
+
+        let c = match self.raw.get(self.pos) {
+            Some(c) => *c,
+            None => return Ok(None),
+        };
+
+        let token = match c {
+            b'{' => self.single(Token::BeginMap),
+            b'}' => self.single(Token::EndMap),
+            b'[' => self.single(Token::BeginList),
+            b']' => self.single(Token::EndList),
+            b',' => self.single(Token::Comma),
+            b':' if self.raw.get(self.pos + 1) == Some(&b':') => self.annotation()?,
+            b':' => self.single(Token::Colon),
+            b'"' => Token::String(self.string()?),
+            b'-' | b'0'..=b'9' => Token::Number(self.number()?),
+            b'a'..=b'z' | b'A'..=b'Z' => self.keyword()?,
+            _ => return Err(DecodeError::new(start, "Unexpected character")),
+        };
+
+        Ok(Some((start, token)))
+    }
+
+    fn single(&mut self, token: Token<'a>) -> Token<'a> {
+        self.pos += 1;
+        token
+    }
+
+    fn word(&mut self) -> &'a str {
+        let start = self.pos;
+        while matches!(
+            self.raw.get(self.pos),
+            Some(b'a'..=b'z' | b'A'..=b'Z' | b'_')
+        ) {
+            self.pos += 1;
+        }
+        // Only ascii letters were consumed
+        std::str::from_utf8(&self.raw[start..self.pos]).unwrap_or_default()
+    }
+
+    fn keyword(&mut self) -> Result<Token<'a>, DecodeError> {
+        let start = self.pos;
+        match self.word() {
+            "null" => Ok(Token::Null),
+            "true" => Ok(Token::Bool(true)),
+            "false" => Ok(Token::Bool(false)),
+            x @ ("NaN" | "Infinity") => Ok(Token::Number(x)),
+            _ => Err(DecodeError::new(start, "Unknown keyword")),
+        }
+    }
+
+    fn annotation(&mut self) -> Result<Token<'a>, DecodeError> {
+        let start = self.pos;
+        self.pos += 2;
+        match self.word() {
+            "" => Err(DecodeError::new(start, "Missing annotation name")),
+            name => Ok(Token::Annotation(name)),
+        }
+    }
+
+    fn digits(&mut self) -> usize {
+        let start = self.pos;
+        while matches!(self.raw.get(self.pos), Some(b'0'..=b'9')) {
+            self.pos += 1;
+        }
+        self.pos - start
+    }
+
+    fn number(&mut self) -> Result<&'a str, DecodeError> {
+        let start = self.pos;
+
+        if self.raw[self.pos] == b'-' {
+            self.pos += 1;
+
+            if self.raw[self.pos..].starts_with(b"Infinity") {
+                self.pos += "Infinity".len();
+                return Ok("-Infinity");
+            }
+        }
+
+        match self.raw.get(self.pos) {
+            Some(b'0') => self.pos += 1,
+            Some(b'1'..=b'9') => {
+                self.digits();
+            }
+            _ => return Err(DecodeError::new(start, "Invalid number")),
+        }
+
+        if self.raw.get(self.pos) == Some(&b'.') {
+            self.pos += 1;
+            if self.digits() == 0 {
+                return Err(DecodeError::new(start, "Invalid number"));
+            }
+        }
+
+        if matches!(self.raw.get(self.pos), Some(b'e' | b'E')) {
+            self.pos += 1;
+            if matches!(self.raw.get(self.pos), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            if self.digits() == 0 {
+                return Err(DecodeError::new(start, "Invalid number"));
+            }
+        }
+
+        // Only ascii characters were consumed
+        Ok(std::str::from_utf8(&self.raw[start..self.pos]).unwrap_or_default())
+    }
+
+    fn string(&mut self) -> Result<Cow<'a, str>, DecodeError> {
+        let start = self.pos;
+        self.pos += 1;
+
+        let mut owned: Option<String> = None;
+        let mut chunk_start = self.pos;
+
+        loop {
+            match self.raw.get(self.pos) {
+                None => return Err(DecodeError::new(start, "Unterminated string")),
+                Some(b'"') => {
+                    let chunk = self.utf8(chunk_start)?;
+                    self.pos += 1;
+                    return Ok(match owned {
+                        Some(mut s) => {
+                            s.push_str(chunk);
+                            Cow::Owned(s)
+                        }
+                        None => Cow::Borrowed(chunk),
+                    });
+                }
+                Some(b'\\') => {
+                    let chunk = self.utf8(chunk_start)?;
+                    let s = owned.get_or_insert_with(String::new);
+                    s.push_str(chunk);
+
+                    let escape_start = self.pos;
+                    self.pos += 1;
+                    let c = match self.raw.get(self.pos) {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => {
+                            self.pos += 1;
+                            let c = self.unicode_escape(escape_start)?;
+                            s.push(c);
+                            chunk_start = self.pos;
+                            continue;
+                        }
+                        _ => return Err(DecodeError::new(escape_start, "Invalid escape")),
+                    };
+                    s.push(c);
+                    self.pos += 1;
+                    chunk_start = self.pos;
+                }
+                Some(0..=0x1f) => {
+                    return Err(DecodeError::new(self.pos, "Control character in string"))
+                }
+                Some(_) => self.pos += 1,
+            }
+        }
+    }
+
+    fn utf8(&self, chunk_start: usize) -> Result<&'a str, DecodeError> {
+        std::str::from_utf8(&self.raw[chunk_start..self.pos])
+            .map_err(|e| DecodeError::new(chunk_start + e.valid_up_to(), "Invalid UTF-8"))
+    }
+
+    fn hex4(&mut self, escape_start: usize) -> Result<u32, DecodeError> {
+        let digits = self
+            .raw
+            .get(self.pos..self.pos + 4)
+            .filter(|x| x.iter().all(u8::is_ascii_hexdigit))
+            .ok_or_else(|| DecodeError::new(escape_start, "Invalid unicode escape"))?;
+        let hex = digits
+            .iter()
+            .filter_map(|d| (*d as char).to_digit(16))
+            .fold(0, |acc, d| acc * 16 + d);
+        self.pos += 4;
+        Ok(hex)
+    }
+
+    fn unicode_escape(&mut self, escape_start: usize) -> Result<char, DecodeError> {
+        let high = self.hex4(escape_start)?;
+
+        let code = match high {
+            0xD800..=0xDBFF => {
+                if self.raw.get(self.pos..self.pos + 2) != Some(b"\\u") {
+                    return Err(DecodeError::new(escape_start, "Unpaired surrogate"));
+                }
+                self.pos += 2;
+                let low = self.hex4(escape_start)?;
+                if !(0xDC00..=0xDFFF).contains(&low) {
+                    return Err(DecodeError::new(escape_start, "Unpaired surrogate"));
+                }
+                0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+            }
+            0xDC00..=0xDFFF => {
+                return Err(DecodeError::new(escape_start, "Unpaired surrogate"));
+            }
+            x => x,
+        };
+
+        char::from_u32(code).ok_or_else(|| DecodeError::new(escape_start, "Invalid unicode escape"))
+    }
+}
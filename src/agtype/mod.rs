@@ -0,0 +1,124 @@
+//! Handling of the agtype text representation.
+//!
+//! Agtype is printed by the AGE as a superset of the JSON. Values may be followed by the type
+//! annotation (`::vertex`, `::edge`, `::path`, `::numeric` etc.)
+//!
+//! ```
+//! use apache_age::{agtype, Vertex};
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct Person<'a> {
+//!     name: &'a str,
+//! }
+//!
+//! let text = r#"{"id": 1, "label": "Person", "properties": {"name": "John"}}::vertex"#;
+//! let person: Vertex<Person> = agtype::from_str(text).unwrap();
+//! assert_eq!(person.properties().name, "John");
+//! ```
+mod de;
+mod lexer;
+mod parser;
+mod value_de;
+
+use postgres_types::Type;
+
+pub use de::{from_slice, from_str, Deserializer};
+
+pub(crate) use de::from_slice_annotated;
+pub(crate) use parser::Parser;
+
+/// Error raised when agtype text cannot be decoded
+#[derive(Debug)]
+pub struct DecodeError {
+    offset: Option<usize>,
+    expected: Option<&'static str>,
+    message: String,
+}
+
+impl DecodeError {
+    pub(crate) fn new(offset: usize, message: impl Into<String>) -> Self {
+        Self {
+            offset: Some(offset),
+            expected: None,
+            message: message.into(),
+        }
+    }
+
+    /// Records the type that was being decoded
+    pub(crate) fn expecting<T: ?Sized>(mut self) -> Self {
+        self.expected.get_or_insert(std::any::type_name::<T>());
+        self
+    }
+
+    /// Attaches offset to the errors raised without it (e.g. by the [`serde::de::Error::custom`])
+    pub(crate) fn or_offset(mut self, offset: usize) -> Self {
+        self.offset.get_or_insert(offset);
+        self
+    }
+
+    /// Byte offset within agtype text, where the problem was detected (if known)
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    /// Name of the type that was being decoded (if known)
+    pub fn expected(&self) -> Option<&'static str> {
+        self.expected
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.offset {
+            Some(offset) => write!(f, "{} at byte {}", self.message, offset),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+impl serde::de::Error for DecodeError {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: std::fmt::Display,
+    {
+        Self {
+            offset: None,
+            expected: None,
+            message: msg.to_string(),
+        }
+    }
+}
+
+/// Validates the agtype header and returns the text representation that follows it
+pub(crate) fn payload<'a>(ty: &Type, raw: &'a [u8]) -> Result<&'a [u8], DecodeError> {
+    if ty.schema() != "ag_catalog" || ty.name() != "agtype" {
+        return Err(serde::de::Error::custom(
+            "Only ag_catalog.agtype is supported",
+        ));
+    }
+
+    match raw.split_first() {
+        // We only support version 1 of the jsonb binary format
+        Some((1, text)) => Ok(text),
+        Some(_) => Err(DecodeError::new(0, "Unsupported JSONB encoding version")),
+        None => Err(DecodeError::new(0, "Empty agtype value")),
+    }
+}
+
+/// Decodes the agtype column, errors remember the requested type
+pub(crate) fn decode_column<'a, T>(
+    ty: &Type,
+    raw: &'a [u8],
+    decode: impl FnOnce(&'a [u8]) -> Result<T, DecodeError>,
+) -> Result<T, Box<dyn std::error::Error + Sync + Send>> {
+    payload(ty, raw)
+        .and_then(decode)
+        .map_err(|e| e.expecting::<T>().into())
+}
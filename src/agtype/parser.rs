@@ -0,0 +1,240 @@
+use super::lexer::{Lexer, Token};
+use super::DecodeError;
+use crate::age_types::{Edge, Path, Vertex};
+use crate::graph_id::GraphId;
+use crate::value::{AgMap, AgValue, Numeric};
+
+/// Maximum nesting of lists and maps, protects stack from malicious inputs
+const MAX_DEPTH: usize = 128;
+
+/// Builds [`AgValue`] out of the agtype text
+pub(crate) struct Parser<'a> {
+    lexer: Lexer<'a>,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    pub(crate) fn new(raw: &'a [u8]) -> Self {
+        Self {
+            lexer: Lexer::new(raw),
+            depth: 0,
+        }
+    }
+
+    /// Parses the whole input as a single value
+    pub(crate) fn parse(raw: &'a [u8]) -> Result<AgValue, DecodeError> {
+        let mut parser = Self::new(raw);
+        let value = parser.value()?;
+        parser.lexer.end()?;
+        Ok(value)
+    }
+
+    fn value(&mut self) -> Result<AgValue, DecodeError> {
+        let (offset, token) = self.lexer.expect_token()?;
+
+        let value = match token {
+            Token::Null => AgValue::Null,
+            Token::Bool(x) => AgValue::Bool(x),
+            Token::String(x) => AgValue::String(x.into_owned()),
+            Token::Number(x) => return self.number(offset, x),
+            Token::BeginMap => AgValue::Map(self.nested(offset, Self::map)?),
+            Token::BeginList => AgValue::List(self.nested(offset, Self::list)?),
+            token => {
+                return Err(DecodeError::new(
+                    offset,
+                    format!("Expected value, found {}", token.describe()),
+                ))
+            }
+        };
+
+        match self.annotation()? {
+            Some((offset, annotation)) => annotate(offset, value, annotation),
+            None => Ok(value),
+        }
+    }
+
+    fn annotation(&mut self) -> Result<Option<(usize, &'a str)>, DecodeError> {
+        if let Some(Token::Annotation(_)) = self.lexer.peek()? {
+            if let Some((offset, Token::Annotation(x))) = self.lexer.next_token()? {
+                return Ok(Some((offset, x)));
+            }
+        }
+        Ok(None)
+    }
+
+    fn nested<T>(
+        &mut self,
+        offset: usize,
+        f: impl FnOnce(&mut Self) -> Result<T, DecodeError>,
+    ) -> Result<T, DecodeError> {
+        if self.depth == MAX_DEPTH {
+            return Err(DecodeError::new(offset, "Recursion limit exceeded"));
+        }
+        self.depth += 1;
+        let result = f(self);
+        self.depth -= 1;
+        result
+    }
+
+    /// Consumes either separator or the closing token. Returns true when collection ends
+    fn separator(&mut self, closing: Token<'a>) -> Result<bool, DecodeError> {
+        let (offset, token) = self.lexer.expect_token()?;
+        if token == Token::Comma {
+            Ok(false)
+        } else if token == closing {
+            Ok(true)
+        } else {
+            Err(DecodeError::new(
+                offset,
+                format!(
+                    "Expected ',' or {}, found {}",
+                    closing.describe(),
+                    token.describe()
+                ),
+            ))
+        }
+    }
+
+    fn map(&mut self) -> Result<AgMap, DecodeError> {
+        let mut map = AgMap::new();
+
+        if self.lexer.peek()? == Some(&Token::EndMap) {
+            self.lexer.next_token()?;
+            return Ok(map);
+        }
+
+        loop {
+            let key = match self.lexer.expect_token()? {
+                (_, Token::String(key)) => key.into_owned(),
+                (offset, token) => {
+                    return Err(DecodeError::new(
+                        offset,
+                        format!("Expected key, found {}", token.describe()),
+                    ))
+                }
+            };
+
+            match self.lexer.expect_token()? {
+                (_, Token::Colon) => {}
+                (offset, token) => {
+                    return Err(DecodeError::new(
+                        offset,
+                        format!("Expected ':', found {}", token.describe()),
+                    ))
+                }
+            }
+
+            map.insert(key, self.value()?);
+
+            if self.separator(Token::EndMap)? {
+                return Ok(map);
+            }
+        }
+    }
+
+    fn list(&mut self) -> Result<Vec<AgValue>, DecodeError> {
+        let mut list = vec![];
+
+        if self.lexer.peek()? == Some(&Token::EndList) {
+            self.lexer.next_token()?;
+            return Ok(list);
+        }
+
+        loop {
+            list.push(self.value()?);
+
+            if self.separator(Token::EndList)? {
+                return Ok(list);
+            }
+        }
+    }
+
+    fn number(&mut self, offset: usize, text: &str) -> Result<AgValue, DecodeError> {
+        let is_float = !text.bytes().all(|c| c == b'-' || c.is_ascii_digit());
+        let float = || {
+            text.parse()
+                .map(AgValue::Float)
+                .map_err(|_| DecodeError::new(offset, "Invalid float"))
+        };
+
+        match self.annotation()? {
+            Some((_, "numeric")) => Ok(AgValue::Numeric(Numeric(text.to_string()))),
+            Some((_, "float")) => float(),
+            Some((_, "integer")) | None if !is_float => text
+                .parse()
+                .map(AgValue::Integer)
+                .map_err(|_| DecodeError::new(offset, "Integer out of range")),
+            None => float(),
+            Some((offset, annotation)) => Err(DecodeError::new(
+                offset,
+                format!("Number cannot be annotated with ::{}", annotation),
+            )),
+        }
+    }
+}
+
+fn annotate(offset: usize, value: AgValue, annotation: &str) -> Result<AgValue, DecodeError> {
+    match (annotation, value) {
+        ("vertex", AgValue::Map(mut map)) => Ok(AgValue::Vertex(Vertex {
+            id: take_id(offset, &mut map, "id")?,
+            label: take_label(offset, &mut map)?,
+            properties: take_properties(offset, &mut map)?,
+        })),
+        ("edge", AgValue::Map(mut map)) => Ok(AgValue::Edge(Edge {
+            id: take_id(offset, &mut map, "id")?,
+            label: take_label(offset, &mut map)?,
+            start_id: take_id(offset, &mut map, "start_id")?,
+            end_id: take_id(offset, &mut map, "end_id")?,
+            properties: take_properties(offset, &mut map)?,
+        })),
+        ("path", AgValue::List(list)) => {
+            let mut vertices = vec![];
+            let mut edges = vec![];
+
+            for (i, element) in list.into_iter().enumerate() {
+                match (i % 2, element) {
+                    (0, AgValue::Vertex(v)) => vertices.push(v),
+                    (1, AgValue::Edge(e)) => edges.push(e),
+                    _ => return Err(DecodeError::new(offset, "Invalid path definition")),
+                }
+            }
+
+            if vertices.len() != edges.len() + 1 {
+                return Err(DecodeError::new(offset, "Invalid path definition"));
+            }
+
+            Ok(AgValue::Path(Path { vertices, edges }))
+        }
+        (annotation, _) => Err(DecodeError::new(
+            offset,
+            format!("Invalid value annotated with ::{}", annotation),
+        )),
+    }
+}
+
+fn take_id(offset: usize, map: &mut AgMap, key: &str) -> Result<GraphId, DecodeError> {
+    match map.remove(key) {
+        Some(AgValue::Integer(x)) if x >= 0 => Ok(GraphId::from(x as u64)),
+        _ => Err(DecodeError::new(
+            offset,
+            format!("Missing or invalid {} field", key),
+        )),
+    }
+}
+
+fn take_label(offset: usize, map: &mut AgMap) -> Result<String, DecodeError> {
+    match map.remove("label") {
+        Some(AgValue::String(x)) => Ok(x),
+        _ => Err(DecodeError::new(offset, "Missing or invalid label field")),
+    }
+}
+
+fn take_properties(offset: usize, map: &mut AgMap) -> Result<AgMap, DecodeError> {
+    match map.remove("properties") {
+        Some(AgValue::Map(x)) => Ok(x),
+        _ => Err(DecodeError::new(
+            offset,
+            "Missing or invalid properties field",
+        )),
+    }
+}
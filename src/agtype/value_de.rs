@@ -0,0 +1,182 @@
+use super::DecodeError;
+use crate::age_types::{Edge, Vertex};
+use crate::value::{AgMap, AgValue};
+use serde::de::value::{MapAccessDeserializer, MapDeserializer, SeqDeserializer};
+use serde::de::{self, IntoDeserializer, Visitor};
+use serde::forward_to_deserialize_any;
+
+fn vertex_entries(vertex: Vertex<AgMap>) -> AgMap {
+    AgMap::from([
+        (
+            "id".to_string(),
+            AgValue::Integer(u64::from(vertex.id) as i64),
+        ),
+        ("label".to_string(), AgValue::String(vertex.label)),
+        ("properties".to_string(), AgValue::Map(vertex.properties)),
+    ])
+}
+
+fn edge_entries(edge: Edge<AgMap>) -> AgMap {
+    AgMap::from([
+        (
+            "id".to_string(),
+            AgValue::Integer(u64::from(edge.id) as i64),
+        ),
+        ("label".to_string(), AgValue::String(edge.label)),
+        (
+            "start_id".to_string(),
+            AgValue::Integer(u64::from(edge.start_id) as i64),
+        ),
+        (
+            "end_id".to_string(),
+            AgValue::Integer(u64::from(edge.end_id) as i64),
+        ),
+        ("properties".to_string(), AgValue::Map(edge.properties)),
+    ])
+}
+
+fn visit_list<'de, V>(list: Vec<AgValue>, visitor: V) -> Result<V::Value, DecodeError>
+where
+    V: Visitor<'de>,
+{
+    let mut seq = SeqDeserializer::new(list.into_iter());
+    let value = visitor.visit_seq(&mut seq)?;
+    seq.end()?;
+    Ok(value)
+}
+
+fn visit_map<'de, V>(map: AgMap, visitor: V) -> Result<V::Value, DecodeError>
+where
+    V: Visitor<'de>,
+{
+    let mut map = MapDeserializer::new(map.into_iter());
+    let value = visitor.visit_map(&mut map)?;
+    map.end()?;
+    Ok(value)
+}
+
+/// Numerics are passed as numbers whenever a number is requested
+macro_rules! deserialize_number {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+            where
+                V: Visitor<'de>,
+            {
+                match self {
+                    AgValue::Numeric(x) => {
+                        if let Ok(x) = x.0.parse::<i64>() {
+                            visitor.visit_i64(x)
+                        } else if let Ok(x) = x.0.parse::<u64>() {
+                            visitor.visit_u64(x)
+                        } else if let Ok(x) = x.0.parse::<f64>() {
+                            visitor.visit_f64(x)
+                        } else {
+                            Err(de::Error::custom(format!("Invalid numeric {}", x.0)))
+                        }
+                    }
+                    x => x.deserialize_any(visitor),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> IntoDeserializer<'de, DecodeError> for AgValue {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+/// Allows to decode [`AgValue`] into any type implementing [`serde::Deserialize`].
+///
+/// Vertices and edges are presented as maps, paths as lists of alternating vertices and edges.
+/// Numerics are presented as strings (so they can be read without precision loss), unless
+/// a number is explicitly requested.
+impl<'de> de::Deserializer<'de> for AgValue {
+    type Error = DecodeError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            AgValue::Null => visitor.visit_unit(),
+            AgValue::Bool(x) => visitor.visit_bool(x),
+            AgValue::Integer(x) => visitor.visit_i64(x),
+            AgValue::Float(x) => visitor.visit_f64(x),
+            AgValue::Numeric(x) => visitor.visit_string(x.0),
+            AgValue::String(x) => visitor.visit_string(x),
+            AgValue::List(x) => visit_list(x, visitor),
+            AgValue::Map(x) => visit_map(x, visitor),
+            AgValue::Vertex(x) => visit_map(vertex_entries(x), visitor),
+            AgValue::Edge(x) => visit_map(edge_entries(x), visitor),
+            AgValue::Path(x) => {
+                let mut list = vec![];
+                let mut edges = x.edges.into_iter();
+                for vertex in x.vertices {
+                    list.push(AgValue::Map(vertex_entries(vertex)));
+                    if let Some(edge) = edges.next() {
+                        list.push(AgValue::Map(edge_entries(edge)));
+                    }
+                }
+                visit_list(list, visitor)
+            }
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            AgValue::Null => visitor.visit_none(),
+            x => visitor.visit_some(x),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            AgValue::String(x) => visitor.visit_enum(x.into_deserializer()),
+            AgValue::Map(x) if x.len() == 1 => visitor.visit_enum(MapAccessDeserializer::new(
+                MapDeserializer::new(x.into_iter()),
+            )),
+            _ => Err(de::Error::custom(
+                "Expected string or map with a single key for an enum",
+            )),
+        }
+    }
+
+    deserialize_number! {
+        deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64 deserialize_i128
+        deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64 deserialize_u128
+        deserialize_f32 deserialize_f64
+    }
+
+    forward_to_deserialize_any! {
+        bool char str string
+        bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
@@ -0,0 +1,291 @@
+#[cfg(any(feature = "sync", feature = "tokio"))]
+use crate::{error::Error, identifier::LabelName, params::CypherParams};
+use serde::ser::{self, Serialize};
+use serde_json::Value;
+
+/// Number of vertices sent at once by `create_vertices`, when no other size is requested
+pub const DEFAULT_INSERT_BATCH_SIZE: usize = 1000;
+
+/// `UNWIND` query creating a vertex out of every row of the `$rows` list
+#[cfg(any(feature = "sync", feature = "tokio"))]
+pub(crate) fn vertices_cypher(label: &str, returning: bool) -> Result<String, Error> {
+    let label = LabelName::new(label)?;
+    let mut cypher = format!("UNWIND $rows AS r CREATE (n:`{}`) SET n = r", label);
+    if returning {
+        cypher.push_str(" RETURN n");
+    }
+
+    Ok(cypher)
+}
+
+/// Serializes the rows lazily into the `$rows` parameters of at most `batch_size` rows.
+/// Yields the number of rows with the parameters
+#[cfg(any(feature = "sync", feature = "tokio"))]
+pub(crate) struct RowBatches<I> {
+    rows: I,
+    batch_size: usize,
+}
+
+#[cfg(any(feature = "sync", feature = "tokio"))]
+impl<I: Iterator> RowBatches<I> {
+    pub(crate) fn new<R>(rows: R, batch_size: Option<usize>) -> Self
+    where
+        R: IntoIterator<IntoIter = I>,
+    {
+        Self {
+            rows: rows.into_iter(),
+            batch_size: batch_size.unwrap_or(DEFAULT_INSERT_BATCH_SIZE).max(1),
+        }
+    }
+}
+
+#[cfg(any(feature = "sync", feature = "tokio"))]
+impl<I> Iterator for RowBatches<I>
+where
+    I: Iterator,
+    I::Item: Serialize,
+{
+    type Item = Result<(u64, CypherParams), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut batch = Vec::new();
+
+        for row in self.rows.by_ref().take(self.batch_size) {
+            match to_value(&row) {
+                Ok(row @ Value::Object(_)) => batch.push(row),
+                Ok(_) => {
+                    return Some(Err(Error::Serialization(
+                        "Vertex properties have to be serialized as a map".to_string(),
+                    )))
+                }
+                Err(e) => return Some(Err(Error::Serialization(e.to_string()))),
+            }
+        }
+
+        if batch.is_empty() {
+            return None;
+        }
+        let count = batch.len() as u64;
+
+        Some(Ok((count, CypherParams::new().param("rows", batch))))
+    }
+}
+
+/// Same as `serde_json::to_value`, but NaN and infinite floats are rejected
+/// instead of being turned into nulls
+pub(crate) fn to_value<T: Serialize + ?Sized>(value: &T) -> Result<Value, serde_json::Error> {
+    value.serialize(FiniteFloats)?;
+    serde_json::to_value(value)
+}
+
+/// Serializer checking that the value has no NaN or infinite floats. Produces nothing
+struct FiniteFloats;
+
+impl FiniteFloats {
+    fn float(v: f64) -> Result<(), serde_json::Error> {
+        if v.is_finite() {
+            Ok(())
+        } else {
+            Err(ser::Error::custom(format!(
+                "{} cannot be serialized as agtype parameter",
+                v
+            )))
+        }
+    }
+}
+
+macro_rules! ignore {
+    ($($method:ident($type:ty)),*) => {
+        $(
+            fn $method(self, _v: $type) -> Result<(), Self::Error> {
+                Ok(())
+            }
+        )*
+    };
+}
+
+impl ser::Serializer for FiniteFloats {
+    type Ok = ();
+    type Error = serde_json::Error;
+
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    ignore!(
+        serialize_bool(bool),
+        serialize_i8(i8),
+        serialize_i16(i16),
+        serialize_i32(i32),
+        serialize_i64(i64),
+        serialize_i128(i128),
+        serialize_u8(u8),
+        serialize_u16(u16),
+        serialize_u32(u32),
+        serialize_u64(u64),
+        serialize_u128(u128),
+        serialize_char(char),
+        serialize_str(&str),
+        serialize_bytes(&[u8]),
+        serialize_unit_struct(&'static str)
+    );
+
+    fn serialize_f32(self, v: f32) -> Result<(), Self::Error> {
+        Self::float(v.into())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<(), Self::Error> {
+        Self::float(v)
+    }
+
+    fn serialize_none(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self, Self::Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self, Self::Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self, Self::Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, Self::Error> {
+        Ok(self)
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self, Self::Error> {
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self, Self::Error> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, Self::Error> {
+        Ok(self)
+    }
+}
+
+macro_rules! check_elements {
+    ($($trait:ident::$method:ident),*) => {
+        $(
+            impl ser::$trait for FiniteFloats {
+                type Ok = ();
+                type Error = serde_json::Error;
+
+                fn $method<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+                    value.serialize(FiniteFloats)
+                }
+
+                fn end(self) -> Result<(), Self::Error> {
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+check_elements!(
+    SerializeSeq::serialize_element,
+    SerializeTuple::serialize_element,
+    SerializeTupleStruct::serialize_field,
+    SerializeTupleVariant::serialize_field
+);
+
+impl ser::SerializeMap for FiniteFloats {
+    type Ok = ();
+    type Error = serde_json::Error;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, _key: &T) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(FiniteFloats)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+macro_rules! check_fields {
+    ($($trait:ident),*) => {
+        $(
+            impl ser::$trait for FiniteFloats {
+                type Ok = ();
+                type Error = serde_json::Error;
+
+                fn serialize_field<T: ?Sized + Serialize>(
+                    &mut self,
+                    _key: &'static str,
+                    value: &T,
+                ) -> Result<(), Self::Error> {
+                    value.serialize(FiniteFloats)
+                }
+
+                fn end(self) -> Result<(), Self::Error> {
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+check_fields!(SerializeStruct, SerializeStructVariant);
@@ -0,0 +1,132 @@
+use crate::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Kind of the label stored in the `ag_label` catalog
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LabelKind {
+    Vertex,
+    Edge,
+}
+
+impl FromStr for LabelKind {
+    type Err = Error;
+
+    fn from_str(kind: &str) -> Result<Self, Error> {
+        match kind {
+            "v" => Ok(LabelKind::Vertex),
+            "e" => Ok(LabelKind::Edge),
+            _ => Err(Error::Decode {
+                offset: None,
+                expected: Some("LabelKind"),
+                message: format!("Unknown label kind {:?}", kind),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for LabelKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LabelKind::Vertex => f.write_str("vertex"),
+            LabelKind::Edge => f.write_str("edge"),
+        }
+    }
+}
+
+/// Label of the graph. See `list_labels` of the clients
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LabelInfo {
+    pub name: String,
+    pub kind: LabelKind,
+}
+
+impl LabelInfo {
+    /// Reads the `name, kind` row of the label query
+    #[cfg(any(feature = "sync", feature = "tokio"))]
+    pub(crate) fn from_row(row: &postgres::Row) -> Result<Self, Error> {
+        Ok(Self {
+            name: row.try_get(0)?,
+            kind: row.try_get::<_, String>(1)?.parse()?,
+        })
+    }
+}
+
+/// Graph stored in the `ag_graph` catalog. See `graph_info` of the clients
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GraphInfo {
+    pub name: String,
+    /// Schema holding the label tables of the graph
+    pub namespace: String,
+    pub oid: u32,
+    pub labels: Vec<LabelInfo>,
+}
+
+impl GraphInfo {
+    /// Reads the `graphid, name, namespace` row of the graph query
+    #[cfg(any(feature = "sync", feature = "tokio"))]
+    pub(crate) fn from_row(row: &postgres::Row, labels: Vec<LabelInfo>) -> Result<Self, Error> {
+        Ok(Self {
+            oid: row.try_get(0)?,
+            name: row.try_get(1)?,
+            namespace: row.try_get(2)?,
+            labels,
+        })
+    }
+}
+
+/// How the number of objects with the label is counted by `graph_stats`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CountMethod {
+    /// `COUNT(*)` of the label table. It scans the whole table
+    #[default]
+    Exact,
+    /// `pg_class.reltuples` of the label table. It is cheap, but only as accurate
+    /// as the last `ANALYZE` (labels never analyzed are reported as empty)
+    Estimate,
+}
+
+/// Number of vertices or edges with the label
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LabelStats {
+    pub name: String,
+    pub kind: LabelKind,
+    pub count: i64,
+}
+
+impl LabelStats {
+    /// Reads the `name, kind, estimate` row of the label statistics query
+    #[cfg(any(feature = "sync", feature = "tokio"))]
+    pub(crate) fn from_row(row: &postgres::Row) -> Result<Self, Error> {
+        Ok(Self {
+            name: row.try_get(0)?,
+            kind: row.try_get::<_, String>(1)?.parse()?,
+            count: row.try_get(2)?,
+        })
+    }
+}
+
+/// Per label statistics of the graph. Default labels (`_ag_label_vertex`, `_ag_label_edge`)
+/// are included, as they hold objects created without a label
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GraphStats {
+    pub labels: Vec<LabelStats>,
+}
+
+impl GraphStats {
+    pub fn vertex_count(&self) -> i64 {
+        self.count(LabelKind::Vertex)
+    }
+
+    pub fn edge_count(&self) -> i64 {
+        self.count(LabelKind::Edge)
+    }
+
+    fn count(&self, kind: LabelKind) -> i64 {
+        self.labels
+            .iter()
+            .filter(|x| x.kind == kind)
+            .map(|x| x.count)
+            .sum()
+    }
+}
@@ -0,0 +1,213 @@
+#[cfg(any(feature = "sync", feature = "tokio"))]
+use crate::constants::{CQ_ARG, CQ_NO_ARG};
+use crate::error::Error;
+#[cfg(any(feature = "sync", feature = "tokio"))]
+use crate::identifier::{dollar_quote, quote_identifier, quote_literal, GraphName};
+
+/// Column used when nothing can be inferred from the query
+pub(crate) const DEFAULT_COLUMN: &str = "v";
+
+/// Clauses that end the list of the returned expressions
+const RETURN_END: [&str; 4] = ["order", "skip", "limit", "union"];
+
+/// Infers names of the columns from the last `RETURN` clause of the cypher query.
+///
+/// * aliased expressions (`count(*) AS total`) use the alias
+/// * variables and properties (`n`, `n.name`) use their text
+/// * other expressions are named after their position (`c3`)
+///
+/// Queries without `RETURN` clause are assumed to return a single `v` column.
+/// Columns of `RETURN *` (or of the empty `RETURN`) cannot be inferred, so they are rejected
+///
+/// ```
+/// use apache_age::return_columns;
+///
+/// assert_eq!(
+///     return_columns("MATCH (n) RETURN n, n.name, count(*) AS total").unwrap(),
+///     ["n", "n.name", "total"]
+/// );
+/// assert!(return_columns("MATCH (n) RETURN *").is_err());
+/// ```
+pub fn return_columns(cypher: &str) -> Result<Vec<String>, Error> {
+    let mask = mask(cypher);
+    let words = words(&mask);
+
+    let start = match words
+        .iter()
+        .rposition(|(_, word)| word.eq_ignore_ascii_case("return"))
+    {
+        Some(i) => i + 1,
+        None => return Ok(vec![DEFAULT_COLUMN.to_string()]),
+    };
+
+    let mut begin = words[start - 1].0 + "return".len();
+    let end = words[start..]
+        .iter()
+        .find(|(_, word)| RETURN_END.iter().any(|x| word.eq_ignore_ascii_case(x)))
+        .map(|(offset, _)| *offset)
+        .unwrap_or(cypher.len());
+
+    if let Some((offset, word)) = words.get(start) {
+        if word.eq_ignore_ascii_case("distinct") && *offset < end {
+            begin = offset + word.len();
+        }
+    }
+
+    if matches!(mask[begin..end].trim(), "*" | "") {
+        return Err(Error::Serialization(
+            "Columns of the RETURN clause cannot be inferred, list them explicitly".to_string(),
+        ));
+    }
+
+    let mut columns: Vec<String> = vec![];
+    let mut item_begin = begin;
+    let separators = mask[begin..end]
+        .match_indices(',')
+        .map(|(i, _)| begin + i)
+        .chain([end]);
+
+    for (i, item_end) in separators.enumerate() {
+        let mut name = column_name(cypher, &mask, item_begin, item_end)
+            .unwrap_or_else(|| format!("c{}", i + 1));
+        if columns.contains(&name) {
+            name = format!("{}_{}", name, i + 1);
+        }
+        columns.push(name);
+        item_begin = item_end + 1;
+    }
+
+    Ok(columns)
+}
+
+/// Renders columns definition used within the `as (...)` part of the cypher call
+#[cfg(any(feature = "sync", feature = "tokio"))]
+pub(crate) fn columns_definition<S: AsRef<str>>(columns: &[S]) -> String {
+    columns
+        .iter()
+        .map(|x| format!("{} agtype", quote_identifier(x.as_ref())))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Cypher call returning the given columns. Graph name is validated,
+/// cypher body is dollar quoted with a random tag
+#[cfg(any(feature = "sync", feature = "tokio"))]
+pub(crate) fn cypher_query<S: AsRef<str>>(
+    graph: &str,
+    cypher: &str,
+    use_arg: bool,
+    columns: &[S],
+) -> Result<String, Error> {
+    let graph = GraphName::new(graph)?;
+    let cypher_arg = if use_arg { CQ_ARG } else { CQ_NO_ARG };
+
+    Ok(format!(
+        cypher_query!(),
+        quote_literal(&graph),
+        dollar_quote(cypher),
+        cypher_arg,
+        columns_definition(columns)
+    ))
+}
+
+/// Cypher call with the columns inferred from the `RETURN` clause
+#[cfg(any(feature = "sync", feature = "tokio"))]
+pub(crate) fn inferred_query(graph: &str, cypher: &str, use_arg: bool) -> Result<String, Error> {
+    cypher_query(graph, cypher, use_arg, &return_columns(cypher)?)
+}
+
+fn column_name(cypher: &str, mask: &str, begin: usize, end: usize) -> Option<String> {
+    let alias = words(&mask[begin..end])
+        .into_iter()
+        .rfind(|(_, word)| word.eq_ignore_ascii_case("as"))
+        .map(|(offset, _)| begin + offset);
+
+    if let Some(alias) = alias {
+        let name = cypher[alias + 2..end].trim();
+        return match name.strip_prefix('`').and_then(|x| x.strip_suffix('`')) {
+            Some(quoted) => Some(quoted.replace("``", "`")),
+            None => Some(name.to_string()),
+        };
+    }
+
+    let expression = cypher[begin..end].trim();
+    let is_property_path = !expression.is_empty()
+        && expression.split('.').all(|part| {
+            part.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+                && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        });
+
+    is_property_path.then(|| expression.to_string())
+}
+
+/// Replaces strings, quoted names, comments and nested expressions with spaces,
+/// so only the top level of the query is left. Byte offsets are preserved
+fn mask(cypher: &str) -> String {
+    mask_literals(cypher, false)
+}
+
+/// Replaces strings, quoted names and comments with spaces. Nested expressions are kept
+/// when `keep_nested` is set. Byte offsets are preserved
+pub(crate) fn mask_literals(cypher: &str, keep_nested: bool) -> String {
+    let bytes = cypher.as_bytes();
+    let mut mask = vec![b' '; bytes.len()];
+    let mut depth = 0usize;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            quote @ (b'\'' | b'"' | b'`') => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != quote {
+                    if bytes[i] == b'\\' && quote != b'`' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i += 2;
+                while i < bytes.len() && !bytes[i..].starts_with(b"*/") {
+                    i += 1;
+                }
+                i += 1;
+            }
+            b'(' | b'[' | b'{' if !keep_nested => depth += 1,
+            b')' | b']' | b'}' if !keep_nested => depth = depth.saturating_sub(1),
+            x if depth == 0 && x.is_ascii() => mask[i] = x,
+            _ => {}
+        }
+        i += 1;
+    }
+
+    String::from_utf8(mask).expect("mask contains only ascii characters")
+}
+
+/// Keywords with their offsets. Properties (`n.return`), parameters and labels are skipped
+fn words(mask: &str) -> Vec<(usize, &str)> {
+    let bytes = mask.as_bytes();
+    let mut words = vec![];
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_' {
+            let begin = i;
+            while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                i += 1;
+            }
+            let preceded = begin > 0 && matches!(bytes[begin - 1], b'.' | b'$' | b':');
+            if !preceded {
+                words.push((begin, &mask[begin..i]));
+            }
+        } else {
+            i += 1;
+        }
+    }
+
+    words
+}
@@ -0,0 +1,274 @@
+use crate::constants::{LOAD_AGE, SET_AGE};
+use crate::error::Error;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Environment variables read by [`AgeConfig::from_env`] with their connection parameters
+const ENV_PARAMS: [(&str, &str); 11] = [
+    ("PGHOST", "host"),
+    ("PGHOSTADDR", "hostaddr"),
+    ("PGPORT", "port"),
+    ("PGUSER", "user"),
+    ("PGPASSWORD", "password"),
+    ("PGDATABASE", "dbname"),
+    ("PGAPPNAME", "application_name"),
+    ("PGOPTIONS", "options"),
+    ("PGCONNECT_TIMEOUT", "connect_timeout"),
+    ("PGSSLMODE", "sslmode"),
+    ("PGTARGETSESSIONATTRS", "target_session_attrs"),
+];
+
+/// How `ag_catalog` is put on the `search_path` of the session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchPath {
+    /// `search_path = ag_catalog, "$user", public`
+    #[default]
+    Replace,
+    /// `ag_catalog` goes before the current `search_path`
+    Prepend,
+    /// `ag_catalog` goes after the current `search_path`
+    Append,
+    /// `search_path` is not changed. Functions and types of AGE have to be reachable anyway
+    Keep,
+}
+
+/// Callback receiving errors of the connection task spawned by the async clients
+pub type ConnectionErrorCallback = Arc<dyn Fn(&Error) + Send + Sync>;
+
+#[derive(Clone)]
+enum Connection {
+    Params(String),
+    Config(Box<tokio_postgres::Config>),
+}
+
+impl fmt::Debug for Connection {
+    /// Prints the parsed config, which redacts the password
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Connection::Params(params) => match params.parse::<tokio_postgres::Config>() {
+                Ok(config) => f.debug_tuple("Params").field(&config).finish(),
+                Err(_) => f.write_str("Params(<invalid>)"),
+            },
+            Connection::Config(config) => f.debug_tuple("Config").field(config).finish(),
+        }
+    }
+}
+
+/// Connection parameters with the setup of the AGE session.
+///
+/// By default it behaves like the plain connection string: `age` is loaded
+/// and `search_path` is replaced. String, `&str` and [`tokio_postgres::Config`]
+/// can be used wherever `AgeConfig` is expected.
+///
+/// ```no_run
+/// use apache_age::sync::{AgeClient, Client};
+/// use apache_age::{AgeConfig, NoTls, SearchPath};
+/// use std::time::Duration;
+///
+/// // age is in the `shared_preload_libraries`, user is not a superuser
+/// let config = AgeConfig::new("host=localhost user=app password=passwd port=8081")
+///     .load_age(false)
+///     .search_path(SearchPath::Prepend)
+///     .statement_timeout(Duration::from_secs(30))
+///     .application_name("graph-importer")
+///     .session_sql("SET work_mem = '64MB'");
+///
+/// let mut client = Client::connect_age(config, NoTls)?;
+/// # Ok::<(), apache_age::Error>(())
+/// ```
+#[derive(Clone)]
+pub struct AgeConfig {
+    connection: Connection,
+    load_age: bool,
+    search_path: SearchPath,
+    statement_timeout: Option<Duration>,
+    application_name: Option<String>,
+    session_sql: Vec<String>,
+    on_connection_error: Option<ConnectionErrorCallback>,
+}
+
+impl AgeConfig {
+    /// Config with the connection string (`host=localhost user=postgres ...` or `postgresql://...`).
+    /// It is parsed on connect
+    pub fn new(params: impl Into<String>) -> Self {
+        Self::with_connection(Connection::Params(params.into()))
+    }
+
+    /// Config with the connection parameters read from the standard `PG*` environment variables
+    /// (`PGHOST`, `PGPORT`, `PGUSER`, `PGPASSWORD`, `PGDATABASE`, `PGAPPNAME`, `PGOPTIONS`,
+    /// `PGCONNECT_TIMEOUT`, `PGSSLMODE`, ...)
+    pub fn from_env() -> Self {
+        let params = ENV_PARAMS
+            .iter()
+            .filter_map(|(env, param)| {
+                let value = std::env::var(env).ok()?;
+                Some(format!("{}={}", param, quote_param(&value)))
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        Self::new(params)
+    }
+
+    fn with_connection(connection: Connection) -> Self {
+        Self {
+            connection,
+            load_age: true,
+            search_path: SearchPath::default(),
+            statement_timeout: None,
+            application_name: None,
+            session_sql: vec![],
+            on_connection_error: None,
+        }
+    }
+
+    /// Runs `LOAD 'age'` on connect (default). It requires superuser,
+    /// so it should be disabled when `age` is preloaded by the server
+    pub fn load_age(mut self, load_age: bool) -> Self {
+        self.load_age = load_age;
+        self
+    }
+
+    pub fn search_path(mut self, search_path: SearchPath) -> Self {
+        self.search_path = search_path;
+        self
+    }
+
+    /// Sets `statement_timeout` of the session (with millisecond precision)
+    pub fn statement_timeout(mut self, timeout: Duration) -> Self {
+        self.statement_timeout = Some(timeout);
+        self
+    }
+
+    /// Overrides `application_name` of the connection parameters
+    pub fn application_name(mut self, application_name: impl Into<String>) -> Self {
+        self.application_name = Some(application_name.into());
+        self
+    }
+
+    /// Adds sql executed after the AGE setup. Statements are run on every new connection
+    /// (and after the session is reset by pools), so they should be idempotent
+    pub fn session_sql(mut self, sql: impl Into<String>) -> Self {
+        self.session_sql.push(sql.into());
+        self
+    }
+
+    /// Sets the callback called when the connection task of the async client
+    /// (`tokio` client or `bb8` pool) fails, e.g. to route the error to the logs
+    /// or to reconnect. The error is returned by the connection `JoinHandle` as well
+    pub fn on_connection_error(
+        mut self,
+        callback: impl Fn(&Error) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_connection_error = Some(Arc::new(callback));
+        self
+    }
+
+    #[cfg(feature = "tokio")]
+    pub(crate) fn connection_error_callback(&self) -> Option<ConnectionErrorCallback> {
+        self.on_connection_error.clone()
+    }
+
+    /// Parsed connection parameters
+    pub fn pg_config(&self) -> Result<tokio_postgres::Config, Error> {
+        let mut config = match &self.connection {
+            Connection::Params(params) => params.parse().map_err(Error::Connection)?,
+            Connection::Config(config) => (**config).clone(),
+        };
+
+        if let Some(application_name) = &self.application_name {
+            config.application_name(application_name);
+        }
+        Ok(config)
+    }
+
+    /// Statements run once the connection is established
+    pub fn setup_query(&self) -> String {
+        let session = self.session_query();
+
+        match (self.load_age, session.is_empty()) {
+            (true, true) => LOAD_AGE.to_string(),
+            (true, false) => format!("{}; {}", LOAD_AGE, session),
+            (false, _) => session,
+        }
+    }
+
+    /// Statements restoring the session settings (without `LOAD 'age'`),
+    /// e.g. after `RESET ALL` or `DISCARD ALL`
+    pub fn session_query(&self) -> String {
+        let mut statements = vec![];
+
+        match self.search_path {
+            SearchPath::Replace => statements.push(SET_AGE.to_string()),
+            SearchPath::Prepend => statements.push(
+                "SELECT set_config('search_path', 'ag_catalog, ' || current_setting('search_path'), false) \
+                 WHERE NOT 'ag_catalog' = ANY(current_schemas(false))"
+                    .to_string(),
+            ),
+            SearchPath::Append => statements.push(
+                "SELECT set_config('search_path', current_setting('search_path') || ', ag_catalog', false) \
+                 WHERE NOT 'ag_catalog' = ANY(current_schemas(false))"
+                    .to_string(),
+            ),
+            SearchPath::Keep => {}
+        }
+
+        if let Some(timeout) = self.statement_timeout {
+            statements.push(format!("SET statement_timeout = {}", timeout.as_millis()));
+        }
+        statements.extend(self.session_sql.iter().cloned());
+
+        statements.join("; ")
+    }
+}
+
+impl fmt::Debug for AgeConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AgeConfig")
+            .field("connection", &self.connection)
+            .field("load_age", &self.load_age)
+            .field("search_path", &self.search_path)
+            .field("statement_timeout", &self.statement_timeout)
+            .field("application_name", &self.application_name)
+            .field("session_sql", &self.session_sql)
+            .field("on_connection_error", &self.on_connection_error.is_some())
+            .finish()
+    }
+}
+
+impl Default for AgeConfig {
+    /// Config with the default connection parameters (same as the empty connection string)
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
+impl From<&str> for AgeConfig {
+    fn from(params: &str) -> Self {
+        Self::new(params)
+    }
+}
+
+impl From<String> for AgeConfig {
+    fn from(params: String) -> Self {
+        Self::new(params)
+    }
+}
+
+impl From<&String> for AgeConfig {
+    fn from(params: &String) -> Self {
+        Self::new(params.as_str())
+    }
+}
+
+impl From<tokio_postgres::Config> for AgeConfig {
+    fn from(config: tokio_postgres::Config) -> Self {
+        Self::with_connection(Connection::Config(Box::new(config)))
+    }
+}
+
+/// Quotes the value of the connection parameter
+fn quote_param(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
@@ -1,31 +1,146 @@
+#[cfg(any(feature = "sync", feature = "tokio"))]
 pub const CREATE_GRAPH: &str = "SELECT * FROM create_graph($1)";
+#[cfg(any(feature = "sync", feature = "tokio"))]
 pub const DROP_GRAPH: &str = "SELECT * FROM drop_graph($1, true)";
+#[cfg(any(feature = "sync", feature = "tokio"))]
 pub const CQ_NO_ARG: &str = "";
+#[cfg(any(feature = "sync", feature = "tokio"))]
 pub const CQ_ARG: &str = ", $1";
 pub const LOAD_AGE: &str = "LOAD 'age'";
 pub const SET_AGE: &str = "SET search_path = ag_catalog, \"$user\", public";
+#[cfg(any(feature = "sync", feature = "tokio"))]
 pub const GRAPH_EXISTS: &str = "SELECT COUNT(name) FROM ag_graph WHERE name = $1";
+#[cfg(any(feature = "sync", feature = "tokio"))]
+pub const LABEL_EXISTS: &str = "SELECT COUNT(l.name) FROM ag_label l \
+    JOIN ag_graph g ON g.graphid = l.graph \
+    WHERE g.name = $1 AND l.name = $2";
+#[cfg(any(feature = "sync", feature = "tokio"))]
+pub const LIST_LABELS: &str = "SELECT l.name::text, l.kind::text FROM ag_label l \
+    JOIN ag_graph g ON g.graphid = l.graph \
+    WHERE g.name = $1 AND l.name NOT IN ('_ag_label_vertex', '_ag_label_edge') \
+    ORDER BY l.id";
+#[cfg(any(feature = "sync", feature = "tokio"))]
+pub const LIST_INDEXES: &str = "SELECT i.relname::text, am.amname::text, x.indisunique, \
+    pg_get_indexdef(x.indexrelid) \
+    FROM ag_label l \
+    JOIN ag_graph g ON g.graphid = l.graph \
+    JOIN pg_index x ON x.indrelid = l.relation \
+    JOIN pg_class i ON i.oid = x.indexrelid \
+    JOIN pg_am am ON am.oid = i.relam \
+    WHERE g.name = $1 AND l.name = $2 \
+    ORDER BY i.relname";
+#[cfg(any(feature = "sync", feature = "tokio"))]
+pub const LIST_CONSTRAINTS: &str = "SELECT c.conname::text, pg_get_constraintdef(c.oid) \
+    FROM ag_label l \
+    JOIN ag_graph g ON g.graphid = l.graph \
+    JOIN pg_constraint c ON c.conrelid = l.relation \
+    WHERE g.name = $1 AND l.name = $2 AND c.contype = 'c' \
+    ORDER BY c.conname";
+#[cfg(any(feature = "sync", feature = "tokio"))]
+pub const MIGRATIONS_LOCK: &str = "SELECT pg_advisory_xact_lock(hashtext($1))";
+#[cfg(any(feature = "sync", feature = "tokio"))]
+pub const CREATE_MIGRATIONS_TABLE: &str = "CREATE TABLE IF NOT EXISTS public.age_migrations (\
+    graph text NOT NULL, \
+    version bigint NOT NULL, \
+    name text NOT NULL, \
+    applied_at timestamptz NOT NULL DEFAULT now(), \
+    PRIMARY KEY (graph, version))";
+#[cfg(any(feature = "sync", feature = "tokio"))]
+pub const MIGRATIONS_TABLE_EXISTS: &str = "SELECT to_regclass('public.age_migrations') IS NOT NULL";
+#[cfg(any(feature = "sync", feature = "tokio"))]
+pub const APPLIED_MIGRATIONS: &str = "SELECT version, name, applied_at \
+    FROM public.age_migrations WHERE graph = $1 ORDER BY version";
+#[cfg(any(feature = "sync", feature = "tokio"))]
+pub const INSERT_MIGRATION: &str =
+    "INSERT INTO public.age_migrations (graph, version, name) VALUES ($1, $2, $3)";
+#[cfg(any(feature = "sync", feature = "tokio"))]
+pub const DELETE_MIGRATION: &str =
+    "DELETE FROM public.age_migrations WHERE graph = $1 AND version = $2";
+#[cfg(any(feature = "sync", feature = "tokio"))]
+pub const LIST_GRAPHS: &str = "SELECT name::text FROM ag_graph ORDER BY name";
+#[cfg(any(feature = "sync", feature = "tokio"))]
+pub const GRAPH_INFO: &str =
+    "SELECT graphid, name::text, namespace::text FROM ag_graph WHERE name = $1";
+#[cfg(any(feature = "sync", feature = "tokio"))]
+pub const LABEL_STATS: &str =
+    "SELECT l.name::text, l.kind::text, GREATEST(c.reltuples, 0)::bigint \
+    FROM ag_label l \
+    JOIN ag_graph g ON g.graphid = l.graph \
+    JOIN pg_class c ON c.oid = l.relation \
+    WHERE g.name = $1 \
+    ORDER BY l.id";
 
+#[cfg(any(feature = "sync", feature = "tokio"))]
 macro_rules! cypher_query {
     () => {
-        "SELECT * FROM cypher('{}', $$ {} $${}) as (v agtype)"
+        "SELECT * FROM cypher({}, {}{}) as ({})"
+    };
+}
+
+#[cfg(any(feature = "sync", feature = "tokio"))]
+macro_rules! create_vlabel {
+    () => {
+        "SELECT create_vlabel({}, {})"
+    };
+}
+
+#[cfg(any(feature = "sync", feature = "tokio"))]
+macro_rules! create_elabel {
+    () => {
+        "SELECT create_elabel({}, {})"
+    };
+}
+
+#[cfg(any(feature = "sync", feature = "tokio"))]
+macro_rules! drop_label {
+    () => {
+        "SELECT drop_label({}, {})"
+    };
+}
+
+#[cfg(any(feature = "sync", feature = "tokio"))]
+macro_rules! count_label {
+    () => {
+        "SELECT COUNT(*) FROM ONLY {}"
     };
 }
 
 macro_rules! constraint {
     () => {
-        "ALTER TABLE \"{}\".\"{}\" ADD CONSTRAINT \"{}\" CHECK({})"
+        "ALTER TABLE {} ADD CONSTRAINT {} CHECK({})"
     };
 }
 
+#[cfg(any(feature = "sync", feature = "tokio"))]
+macro_rules! drop_constraint {
+    () => {
+        "ALTER TABLE {} DROP CONSTRAINT {}"
+    };
+}
+
+#[cfg(any(feature = "sync", feature = "tokio"))]
 macro_rules! unique_index {
     () => {
-        "CREATE UNIQUE INDEX \"{}\" ON \"{}\".\"{}\"(agtype_access_operator(properties, '\"{}\"'))"
+        "CREATE UNIQUE INDEX {} ON {}(agtype_access_operator(properties, {}))"
+    };
+}
+
+macro_rules! create_index {
+    () => {
+        "CREATE {}INDEX {} ON {} USING {} ({})"
+    };
+}
+
+#[cfg(any(feature = "sync", feature = "tokio"))]
+macro_rules! drop_index {
+    () => {
+        "DROP INDEX {}.{}"
     };
 }
 
+#[cfg(any(feature = "sync", feature = "tokio"))]
 macro_rules! required_constraint {
     () => {
-        "agtype_access_operator(properties, '\"{}\"') IS NOT NULL"
+        "agtype_access_operator(properties, {}) IS NOT NULL"
     };
 }
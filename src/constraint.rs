@@ -0,0 +1,237 @@
+use crate::error::Error;
+use crate::identifier::{label_table, property_access, quote_literal, quote_name};
+use std::ops::{Bound, RangeBounds};
+
+/// Agtype text of the numbers, with the optional `::numeric` suffix (e.g. `12.5::numeric`)
+const NUMBER_PATTERN: &str = "^-?[0-9]+(\\.[0-9]+)?(e[-+]?[0-9]+)?(::numeric)?$";
+
+/// Type of the property checked by [`Constraint::of_type`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PropertyType {
+    String,
+    Int,
+    Bool,
+}
+
+/// Named `CHECK` constraint on the label table, created with `create_constraint` of the clients.
+///
+/// Property paths are dot separated (`address.city`). Every check is joined with `AND`.
+/// Missing properties pass all checks except [`Constraint::required`], while properties
+/// of the other type fail the string and numeric checks.
+///
+/// ```
+/// use apache_age::{Constraint, PropertyType};
+///
+/// let person = Constraint::new("person_check")
+///     .required("name")
+///     .of_type("name", PropertyType::String)
+///     .length("name", 1..=64)
+///     .range("age", 0.0..150.0)
+///     .matches("email", "^[^@]+@[^@]+$")
+///     .one_of("status", ["active", "banned"])
+///     .of_type("address.zip", PropertyType::Int);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constraint {
+    name: String,
+    checks: Vec<String>,
+    error: Option<String>,
+}
+
+impl Constraint {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            checks: vec![],
+            error: None,
+        }
+    }
+
+    /// Property has to be set
+    pub fn required(mut self, path: &str) -> Self {
+        self.checks
+            .push(format!("{} IS NOT NULL", property_access(path)));
+        self
+    }
+
+    /// Property has to be of the certain type (if set)
+    pub fn of_type(mut self, path: &str, property_type: PropertyType) -> Self {
+        let text = text(path);
+        self.checks.push(match property_type {
+            PropertyType::String => format!("left({}, 1) = '\"'", text),
+            PropertyType::Int => format!("{} ~ '^-?[0-9]+$'", text),
+            PropertyType::Bool => format!("{} IN ('true', 'false')", text),
+        });
+        self
+    }
+
+    /// Property has to be a number within the range (if set)
+    pub fn range(mut self, path: &str, range: impl RangeBounds<f64>) -> Self {
+        let value = number(path);
+        let mut conditions = vec![];
+
+        for (bound, inclusive, exclusive) in [
+            (range.start_bound(), ">=", ">"),
+            (range.end_bound(), "<=", "<"),
+        ] {
+            let (operator, limit) = match bound {
+                Bound::Included(x) => (inclusive, *x),
+                Bound::Excluded(x) => (exclusive, *x),
+                Bound::Unbounded => continue,
+            };
+            if !limit.is_finite() {
+                self.error
+                    .get_or_insert(format!("{}: range bounds have to be finite", path));
+                return self;
+            }
+            conditions.push(format!("{} {} {}", value, operator, limit));
+        }
+
+        if conditions.is_empty() {
+            conditions.push(format!("{} IS NOT NULL", value));
+        }
+        self.checks
+            .push(or_missing(path, &conditions.join(" AND ")));
+        self
+    }
+
+    /// Property has to be a string matching the (postgres) regular expression (if set)
+    pub fn matches(mut self, path: &str, pattern: &str) -> Self {
+        let condition = format!("{} ~ {}", string(path), quote_literal(pattern));
+        self.checks.push(or_missing(path, &condition));
+        self
+    }
+
+    /// Property has to be a string with the number of characters within the range (if set)
+    pub fn length(mut self, path: &str, range: impl RangeBounds<usize>) -> Self {
+        let value = format!("char_length({})", string(path));
+        let mut conditions = vec![format!("{} IS NOT NULL", value)];
+
+        match range.start_bound() {
+            Bound::Included(x) => conditions.push(format!("{} >= {}", value, x)),
+            Bound::Excluded(x) => conditions.push(format!("{} > {}", value, x)),
+            Bound::Unbounded => {}
+        }
+        match range.end_bound() {
+            Bound::Included(x) => conditions.push(format!("{} <= {}", value, x)),
+            Bound::Excluded(x) => conditions.push(format!("{} < {}", value, x)),
+            Bound::Unbounded => {}
+        }
+
+        self.checks
+            .push(or_missing(path, &conditions.join(" AND ")));
+        self
+    }
+
+    /// Property has to be equal to one of the values (if set)
+    pub fn one_of<T, I>(mut self, path: &str, values: I) -> Self
+    where
+        T: Into<serde_json::Value>,
+        I: IntoIterator<Item = T>,
+    {
+        let values = values
+            .into_iter()
+            .map(|x| format!("{}::agtype", quote_literal(&x.into().to_string())))
+            .collect::<Vec<_>>();
+
+        if values.is_empty() {
+            self.error
+                .get_or_insert(format!("{}: no values to choose from", path));
+        } else {
+            self.checks.push(format!(
+                "{} IN ({})",
+                property_access(path),
+                values.join(", ")
+            ));
+        }
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Boolean expression of the `CHECK`
+    pub fn expression(&self) -> Result<String, Error> {
+        if let Some(e) = &self.error {
+            return Err(Error::InvalidSchema(e.clone()));
+        }
+        if self.checks.is_empty() {
+            return Err(Error::InvalidSchema(format!(
+                "Constraint {:?} has no checks",
+                self.name
+            )));
+        }
+
+        Ok(self.checks.join(" AND "))
+    }
+
+    /// `ALTER TABLE ... ADD CONSTRAINT` statement for the label. Graph and label are validated,
+    /// name and property keys are quoted
+    pub fn to_sql(&self, graph: &str, label: &str) -> Result<String, Error> {
+        Ok(format!(
+            constraint!(),
+            label_table(graph, label)?,
+            quote_name(&self.name)?,
+            self.expression()?
+        ))
+    }
+}
+
+/// `CHECK` constraint of the label table. See `list_constraints` of the clients
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ConstraintInfo {
+    pub name: String,
+    /// Definition of the constraint (`pg_get_constraintdef`), e.g. `CHECK (...)`
+    pub definition: String,
+}
+
+impl ConstraintInfo {
+    /// Reads the `name, definition` row of the constraint query
+    #[cfg(any(feature = "sync", feature = "tokio"))]
+    pub(crate) fn from_row(row: &postgres::Row) -> Result<Self, Error> {
+        Ok(Self {
+            name: row.try_get(0)?,
+            definition: row.try_get(1)?,
+        })
+    }
+}
+
+/// `ALTER TABLE ... DROP CONSTRAINT` statement
+#[cfg(any(feature = "sync", feature = "tokio"))]
+pub(crate) fn drop_constraint_query(graph: &str, label: &str, name: &str) -> Result<String, Error> {
+    Ok(format!(
+        drop_constraint!(),
+        label_table(graph, label)?,
+        quote_name(name)?
+    ))
+}
+
+/// Agtype text of the property, e.g. `"John"` for strings
+fn text(path: &str) -> String {
+    format!("({})::text", property_access(path))
+}
+
+/// Unquoted value of the string property, `NULL` for other types
+fn string(path: &str) -> String {
+    let text = text(path);
+    format!(
+        "(CASE WHEN left({0}, 1) = '\"' THEN {0}::jsonb #>> '{{}}' END)",
+        text
+    )
+}
+
+/// Value of the numeric property, `NULL` for other types
+fn number(path: &str) -> String {
+    let text = text(path);
+    format!(
+        "(CASE WHEN {0} ~ {1} THEN regexp_replace({0}, '::numeric$', '')::numeric END)",
+        text,
+        quote_literal(NUMBER_PATTERN)
+    )
+}
+
+/// Condition passing when the property is missing. Properties of the other type fail it
+fn or_missing(path: &str, condition: &str) -> String {
+    format!("COALESCE({}, {} IS NULL)", condition, property_access(path))
+}
@@ -0,0 +1,188 @@
+use crate::agtype::DecodeError;
+use postgres::error::SqlState;
+use std::error::Error as StdError;
+use std::fmt;
+
+/// Errors returned by the clients, decoders and serializers of this crate
+///
+/// ```no_run
+/// use apache_age::sync::{AgeClient, Client};
+/// use apache_age::{Error, NoTls};
+/// use postgres::error::SqlState;
+///
+/// let mut client = Client::connect_age(
+///     "host=localhost user=postgres password=passwd port=8081",
+///     NoTls,
+/// )?;
+///
+/// match client.execute_cypher::<()>("graph", "CREATE (n: Person {name: 'John'})", None) {
+///     Ok(_) => {}
+///     Err(Error::MissingGraph(name)) => println!("create {} first", name),
+///     Err(Error::Database { sqlstate, .. }) if sqlstate == SqlState::UNIQUE_VIOLATION => {}
+///     Err(e) => return Err(e),
+/// }
+/// # Ok::<(), Error>(())
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// Connection could not be established or was lost
+    Connection(postgres::Error),
+
+    /// Statement was rejected by the database
+    Database {
+        sqlstate: SqlState,
+        source: postgres::Error,
+    },
+
+    /// Agtype value could not be decoded
+    Decode {
+        /// Byte offset within the agtype text (if known)
+        offset: Option<usize>,
+        /// Name of the type that was being decoded (if known)
+        expected: Option<&'static str>,
+        message: String,
+    },
+
+    /// Value could not be serialized into agtype or cypher
+    Serialization(String),
+
+    /// Name cannot be used as a graph, label or column identifier
+    InvalidIdentifier(String),
+
+    /// Graph used by the query does not exist
+    MissingGraph(String),
+
+    /// Parameters do not match the `$placeholders` of the query
+    InvalidParameters {
+        /// Placeholders without a value
+        missing: Vec<String>,
+        /// Values without a placeholder
+        unused: Vec<String>,
+    },
+
+    /// Index or constraint definition is invalid (e.g. no keys or checks)
+    InvalidSchema(String),
+
+    /// Migrations are inconsistent (e.g. duplicated version, missing down step)
+    /// or cannot be loaded
+    Migration(String),
+
+    /// Any other client error (e.g. unexpected number of rows, missing column)
+    Postgres(postgres::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Connection(e) => write!(f, "Connection error: {}", e),
+            Error::Database { source, .. } => match source.as_db_error() {
+                Some(db) => write!(f, "Database error: {}", db),
+                None => write!(f, "Database error: {}", source),
+            },
+            Error::Decode {
+                offset,
+                expected,
+                message,
+            } => {
+                f.write_str("Cannot decode agtype")?;
+                if let Some(expected) = expected {
+                    write!(f, " into {}", expected)?;
+                }
+                write!(f, ": {}", message)?;
+                if let Some(offset) = offset {
+                    write!(f, " at byte {}", offset)?;
+                }
+                Ok(())
+            }
+            Error::Serialization(message) => write!(f, "Serialization error: {}", message),
+            Error::InvalidIdentifier(name) => write!(f, "Invalid identifier: {:?}", name),
+            Error::MissingGraph(name) => write!(f, "Graph {:?} does not exist", name),
+            Error::InvalidParameters { missing, unused } => {
+                f.write_str("Invalid cypher parameters")?;
+                if !missing.is_empty() {
+                    write!(f, ", missing: {}", missing.join(", "))?;
+                }
+                if !unused.is_empty() {
+                    write!(f, ", unused: {}", unused.join(", "))?;
+                }
+                Ok(())
+            }
+            Error::InvalidSchema(message) => write!(f, "Invalid schema: {}", message),
+            Error::Migration(message) => write!(f, "Migration error: {}", message),
+            Error::Postgres(e) => fmt::Display::fmt(e, f),
+        }
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Error::Connection(e) | Error::Database { source: e, .. } | Error::Postgres(e) => {
+                Some(e)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl From<DecodeError> for Error {
+    fn from(e: DecodeError) -> Self {
+        Error::Decode {
+            offset: e.offset(),
+            expected: e.expected(),
+            message: e.message().to_string(),
+        }
+    }
+}
+
+/// Errors raised by the decoders and encoders are recovered from the postgres error source
+impl From<postgres::Error> for Error {
+    fn from(e: postgres::Error) -> Self {
+        if let Some(source) = e.source() {
+            if let Some(decode) = source.downcast_ref::<DecodeError>() {
+                return Error::Decode {
+                    offset: decode.offset(),
+                    expected: decode.expected(),
+                    message: decode.message().to_string(),
+                };
+            }
+            if let Some(Error::Serialization(message)) = source.downcast_ref::<Error>() {
+                return Error::Serialization(message.clone());
+            }
+        }
+
+        if let Some(db) = e.as_db_error() {
+            if db.code() == &SqlState::INVALID_SCHEMA_NAME {
+                let graph = db
+                    .message()
+                    .strip_prefix("graph \"")
+                    .and_then(|x| x.strip_suffix("\" does not exist"));
+                if let Some(graph) = graph {
+                    return Error::MissingGraph(graph.to_string());
+                }
+            }
+
+            return Error::Database {
+                sqlstate: db.code().clone(),
+                source: e,
+            };
+        }
+
+        let is_io = e.source().is_some_and(|x| x.is::<std::io::Error>());
+        if e.is_closed() || is_io {
+            Error::Connection(e)
+        } else {
+            Error::Postgres(e)
+        }
+    }
+}
+
+impl serde::ser::Error for Error {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Error::Serialization(msg.to_string())
+    }
+}
@@ -0,0 +1,97 @@
+use crate::agtype::DecodeError;
+use bytes::BufMut;
+use postgres_types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
+use serde::{Deserialize, Serialize};
+
+const ENTRY_ID_BITS: u32 = 48;
+const ENTRY_ID_MASK: u64 = (1 << ENTRY_ID_BITS) - 1;
+
+/// Identifier of the vertex or edge (`graphid` within AGE).
+///
+/// Upper 16 bits are the id of the label, the lower 48 bits - id of the entry within label table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct GraphId(u64);
+
+impl GraphId {
+    /// Entry id is truncated to the 48 bits
+    pub fn new(label_id: u16, entry_id: u64) -> Self {
+        Self(((label_id as u64) << ENTRY_ID_BITS) | (entry_id & ENTRY_ID_MASK))
+    }
+
+    /// Id of the label (`ag_label.id`) the entry belongs to
+    pub fn label_id(&self) -> u16 {
+        (self.0 >> ENTRY_ID_BITS) as u16
+    }
+
+    /// Id of the entry within the label table
+    pub fn entry_id(&self) -> u64 {
+        self.0 & ENTRY_ID_MASK
+    }
+}
+
+impl From<u64> for GraphId {
+    fn from(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<GraphId> for u64 {
+    fn from(value: GraphId) -> Self {
+        value.0
+    }
+}
+
+impl std::fmt::Display for GraphId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::str::FromStr for GraphId {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Self)
+    }
+}
+
+impl ToSql for GraphId {
+    fn accepts(ty: &Type) -> bool {
+        (ty.schema() == "ag_catalog" && ty.name() == "graphid") || *ty == Type::INT8
+    }
+
+    fn to_sql(
+        &self,
+        _ty: &Type,
+        out: &mut postgres_types::private::BytesMut,
+    ) -> Result<postgres_types::IsNull, Box<dyn std::error::Error + Sync + Send>>
+    where
+        Self: Sized,
+    {
+        out.put_u64(self.0);
+        Ok(IsNull::No)
+    }
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for GraphId {
+    fn from_sql(
+        _ty: &Type,
+        raw: &'a [u8],
+    ) -> Result<GraphId, Box<dyn std::error::Error + Sync + Send>> {
+        match <[u8; 8]>::try_from(raw) {
+            Ok(b) => Ok(GraphId(u64::from_be_bytes(b))),
+            Err(_) => Err(
+                DecodeError::new(0, format!("Invalid graphid length {}", raw.len()))
+                    .expecting::<GraphId>()
+                    .into(),
+            ),
+        }
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        (ty.schema() == "ag_catalog" && ty.name() == "graphid") || *ty == Type::INT8
+    }
+}
@@ -0,0 +1,202 @@
+use crate::error::Error;
+#[cfg(any(feature = "sync", feature = "tokio"))]
+use rand::{distributions::Alphanumeric, Rng};
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+
+/// Longest identifier accepted by postgres (`NAMEDATALEN - 1`)
+pub(crate) const MAX_IDENTIFIER_LEN: usize = 63;
+
+/// Length of the random part of the dollar quote tag
+#[cfg(any(feature = "sync", feature = "tokio"))]
+const DOLLAR_TAG_LEN: usize = 8;
+
+macro_rules! identifier {
+    ($(#[$doc:meta])* $name:ident, $validate:ident) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub struct $name(String);
+
+        impl $name {
+            /// Validates the name. Returns [`Error::InvalidIdentifier`] if it is not allowed
+            pub fn new(name: impl Into<String>) -> Result<Self, Error> {
+                let name = name.into();
+                if $validate(&name) {
+                    Ok(Self(name))
+                } else {
+                    Err(Error::InvalidIdentifier(name))
+                }
+            }
+
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            pub fn into_inner(self) -> String {
+                self.0
+            }
+        }
+
+        impl Deref for $name {
+            type Target = str;
+
+            fn deref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = Error;
+
+            fn from_str(name: &str) -> Result<Self, Error> {
+                Self::new(name)
+            }
+        }
+
+        impl TryFrom<&str> for $name {
+            type Error = Error;
+
+            fn try_from(name: &str) -> Result<Self, Error> {
+                Self::new(name)
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = Error;
+
+            fn try_from(name: String) -> Result<Self, Error> {
+                Self::new(name)
+            }
+        }
+    };
+}
+
+identifier! {
+    /// Name of the graph, validated with the same rules as AGE uses:
+    /// 3 to 63 bytes, starts with a letter or `_`, contains only letters, digits, `_`, `.` or `-`
+    /// and does not end with `.` or `-`
+    ///
+    /// ```
+    /// use apache_age::GraphName;
+    ///
+    /// assert!(GraphName::new("social_graph").is_ok());
+    /// assert!(GraphName::new("graph'); DROP TABLE users; --").is_err());
+    /// ```
+    GraphName,
+    is_graph_name
+}
+
+identifier! {
+    /// Name of the vertex or edge label: 1 to 63 bytes, starts with a letter or `_`
+    /// and contains only letters, digits or `_`
+    ///
+    /// ```
+    /// use apache_age::LabelName;
+    ///
+    /// assert!(LabelName::new("Person").is_ok());
+    /// assert!(LabelName::new("Person\" CASCADE").is_err());
+    /// ```
+    LabelName,
+    is_label_name
+}
+
+fn is_graph_name(name: &str) -> bool {
+    let inner = |c: char| c.is_alphanumeric() || matches!(c, '_' | '.' | '-');
+
+    (3..=MAX_IDENTIFIER_LEN).contains(&name.len())
+        && name.starts_with(|c: char| c.is_alphabetic() || c == '_')
+        && name.ends_with(|c: char| c.is_alphanumeric() || c == '_')
+        && name.chars().all(inner)
+}
+
+fn is_label_name(name: &str) -> bool {
+    (1..=MAX_IDENTIFIER_LEN).contains(&name.len())
+        && name.starts_with(|c: char| c.is_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Quotes any name (index, constraint, column) as the sql identifier.
+/// Empty names and names with `NUL` cannot be quoted
+pub(crate) fn quote_name(name: &str) -> Result<String, Error> {
+    if name.is_empty() || name.contains('\0') {
+        return Err(Error::InvalidIdentifier(name.to_string()));
+    }
+
+    Ok(quote_identifier(name))
+}
+
+pub(crate) fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+pub(crate) fn quote_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Wraps the text with the dollar quotes. Tag is randomized,
+/// so the text cannot close the quote on its own
+#[cfg(any(feature = "sync", feature = "tokio"))]
+pub(crate) fn dollar_quote(text: &str) -> String {
+    loop {
+        let tag: String = rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(DOLLAR_TAG_LEN)
+            .map(char::from)
+            .collect();
+        let tag = format!("$age_{}$", tag);
+
+        if !text.contains(&tag) {
+            return format!("{} {} {}", tag, text, tag);
+        }
+    }
+}
+
+/// Label table of the graph, e.g. `"graph"."Person"`
+pub(crate) fn label_table(graph: &str, label: &str) -> Result<String, Error> {
+    let graph = GraphName::new(graph)?;
+    let label = LabelName::new(label)?;
+
+    Ok(format!(
+        "{}.{}",
+        quote_identifier(&graph),
+        quote_identifier(&label)
+    ))
+}
+
+/// Property key as the agtype string literal, e.g. `'"name"'`
+pub(crate) fn property_literal(field: &str) -> String {
+    quote_literal(&serde_json::Value::from(field).to_string())
+}
+
+/// Property under the dot separated path, e.g. `agtype_access_operator(properties, '"a"', '"b"')`
+pub(crate) fn property_access(path: &str) -> String {
+    let keys = path
+        .split('.')
+        .map(property_literal)
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!("agtype_access_operator(properties, {})", keys)
+}
+
+/// Graph and label as the sql string literals, e.g. `'graph'` and `'Person'`
+#[cfg(any(feature = "sync", feature = "tokio"))]
+pub(crate) fn label_literals(graph: &str, label: &str) -> Result<(String, String), Error> {
+    let graph = GraphName::new(graph)?;
+    let label = LabelName::new(label)?;
+
+    Ok((quote_literal(&graph), quote_literal(&label)))
+}
@@ -0,0 +1,196 @@
+use crate::error::Error;
+use crate::identifier::{label_table, property_access, quote_name};
+
+/// Column or expression of the label table covered by the index
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum IndexKey {
+    /// Property under the dot separated path, same as within the [`Constraint`](crate::Constraint),
+    /// e.g. `agtype_access_operator(properties, '"address"', '"city"')`
+    Property(String),
+    /// Whole `properties` map. Used by the GIN indexes for the containment queries
+    Properties,
+    /// `id` of the vertex or edge
+    Id,
+    /// `start_id` of the edge label
+    StartId,
+    /// `end_id` of the edge label
+    EndId,
+}
+
+impl IndexKey {
+    fn to_sql(&self) -> String {
+        match self {
+            IndexKey::Property(path) => property_access(path),
+            IndexKey::Properties => "properties".to_string(),
+            IndexKey::Id => "id".to_string(),
+            IndexKey::StartId => "start_id".to_string(),
+            IndexKey::EndId => "end_id".to_string(),
+        }
+    }
+}
+
+/// Access method of the index
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum IndexMethod {
+    #[default]
+    BTree,
+    Gin,
+}
+
+impl IndexMethod {
+    fn as_sql(&self) -> &'static str {
+        match self {
+            IndexMethod::BTree => "btree",
+            IndexMethod::Gin => "gin",
+        }
+    }
+}
+
+/// Index on the label table, created with `create_index` of the clients.
+/// Keys are indexed in the order they were added
+///
+/// ```
+/// use apache_age::Index;
+///
+/// // composite, non-unique property index
+/// let by_name = Index::new("person_name_idx").property("surname").property("name");
+/// // unique property index
+/// let by_email = Index::new("person_email_idx").property("email").unique(true);
+/// // containment queries, e.g. `MATCH (n: Person {city: 'Paris'})`
+/// let by_properties = Index::gin("person_properties_idx");
+/// // edge traversal
+/// let by_start = Index::new("knows_start_idx").start_id();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Index {
+    name: String,
+    keys: Vec<IndexKey>,
+    method: IndexMethod,
+    unique: bool,
+}
+
+impl Index {
+    /// B-tree index without any keys
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            keys: vec![],
+            method: IndexMethod::default(),
+            unique: false,
+        }
+    }
+
+    /// GIN index on the whole `properties` map
+    pub fn gin(name: impl Into<String>) -> Self {
+        Self::new(name)
+            .method(IndexMethod::Gin)
+            .key(IndexKey::Properties)
+    }
+
+    pub fn key(mut self, key: IndexKey) -> Self {
+        self.keys.push(key);
+        self
+    }
+
+    pub fn property(self, field: impl Into<String>) -> Self {
+        self.key(IndexKey::Property(field.into()))
+    }
+
+    pub fn start_id(self) -> Self {
+        self.key(IndexKey::StartId)
+    }
+
+    pub fn end_id(self) -> Self {
+        self.key(IndexKey::EndId)
+    }
+
+    pub fn method(mut self, method: IndexMethod) -> Self {
+        self.method = method;
+        self
+    }
+
+    pub fn unique(mut self, unique: bool) -> Self {
+        self.unique = unique;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn keys(&self) -> &[IndexKey] {
+        &self.keys
+    }
+
+    /// `CREATE INDEX` statement for the label. Graph and label are validated,
+    /// name and property keys are quoted. Indexes without keys and GIN indexes
+    /// on anything but the `properties` map are rejected
+    pub fn to_sql(&self, graph: &str, label: &str) -> Result<String, Error> {
+        if self.keys.is_empty() {
+            return Err(Error::InvalidSchema(format!(
+                "Index {:?} has no keys",
+                self.name
+            )));
+        }
+        if self.method == IndexMethod::Gin && self.keys != [IndexKey::Properties] {
+            return Err(Error::InvalidSchema(format!(
+                "GIN index {:?} can cover only the properties map",
+                self.name
+            )));
+        }
+
+        let keys = self
+            .keys
+            .iter()
+            .map(IndexKey::to_sql)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        Ok(format!(
+            create_index!(),
+            if self.unique { "UNIQUE " } else { "" },
+            quote_name(&self.name)?,
+            label_table(graph, label)?,
+            self.method.as_sql(),
+            keys
+        ))
+    }
+}
+
+/// Index of the label table. See `list_indexes` of the clients
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IndexInfo {
+    pub name: String,
+    /// Access method, e.g. `btree` or `gin`
+    pub method: String,
+    pub unique: bool,
+    /// Statement recreating the index (`pg_get_indexdef`)
+    pub definition: String,
+}
+
+impl IndexInfo {
+    /// Reads the `name, method, unique, definition` row of the index query
+    #[cfg(any(feature = "sync", feature = "tokio"))]
+    pub(crate) fn from_row(row: &postgres::Row) -> Result<Self, Error> {
+        Ok(Self {
+            name: row.try_get(0)?,
+            method: row.try_get(1)?,
+            unique: row.try_get(2)?,
+            definition: row.try_get(3)?,
+        })
+    }
+}
+
+/// `DROP INDEX` statement. Indexes live in the schema of the graph
+#[cfg(any(feature = "sync", feature = "tokio"))]
+pub(crate) fn drop_index_query(graph: &str, name: &str) -> Result<String, Error> {
+    use crate::identifier::{quote_identifier, GraphName};
+
+    let graph = GraphName::new(graph)?;
+
+    Ok(format!(
+        drop_index!(),
+        quote_identifier(&graph),
+        quote_name(name)?
+    ))
+}
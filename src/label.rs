@@ -0,0 +1,162 @@
+use crate::catalog::LabelKind;
+use crate::constraint::Constraint;
+#[cfg(any(feature = "sync", feature = "tokio"))]
+use crate::error::Error;
+use crate::identifier::MAX_IDENTIFIER_LEN;
+use crate::index::Index;
+
+/// Struct bound to the vertex or edge label of the graph.
+///
+/// Can be derived with the `derive` feature. Label defaults to the struct name,
+/// `#[age_label(label = "...")]` overrides it and `#[age_label(edge)]` binds the struct
+/// to the edge label. Fields marked with `#[age_label(required)]` or `#[age_label(unique)]`
+/// get the required constraint or the unique index (`#[age_label(rename = "...")]` overrides
+/// the property name)
+///
+/// ```ignore
+/// #[derive(Serialize, Deserialize, AgeLabel)]
+/// #[age_label(label = "Person")]
+/// struct Person {
+///     #[age_label(required)]
+///     name: String,
+///     #[age_label(unique)]
+///     email: String,
+/// }
+///
+/// Person::ensure_schema(&mut client, "graph")?;
+/// let query = format!("MATCH (n: {}) RETURN n", Person::LABEL);
+/// ```
+pub trait Label {
+    const LABEL: &'static str;
+    const KIND: LabelKind;
+    /// Properties that have to be set
+    const REQUIRED: &'static [&'static str];
+    /// Properties with the unique index
+    const UNIQUE: &'static [&'static str];
+
+    /// Name of the unique index of the property (`<label>_<property>_unique`).
+    /// Names longer than 63 bytes are truncated and end with the hash of the full name
+    fn unique_index_name(property: &str) -> String {
+        schema_name(Self::LABEL, property, "unique")
+    }
+
+    /// Name of the required constraint of the property (`<label>_<property>_required`).
+    /// Names longer than 63 bytes are truncated and end with the hash of the full name
+    fn required_constraint_name(property: &str) -> String {
+        schema_name(Self::LABEL, property, "required")
+    }
+
+    /// Required constraints of the label, one per property
+    fn constraints() -> Vec<Constraint> {
+        Self::REQUIRED
+            .iter()
+            .map(|x| Constraint::new(Self::required_constraint_name(x)).required(x))
+            .collect()
+    }
+
+    /// Unique indexes of the label, one per property
+    fn indexes() -> Vec<Index> {
+        Self::UNIQUE
+            .iter()
+            .map(|x| {
+                Index::new(Self::unique_index_name(x))
+                    .property(*x)
+                    .unique(true)
+            })
+            .collect()
+    }
+
+    /// Creates the label with its constraints and indexes, unless they already exist
+    #[cfg(feature = "sync")]
+    fn ensure_schema<C>(client: &mut C, graph: &str) -> Result<(), Error>
+    where
+        Self: Sized,
+        C: crate::sync::AgeClient,
+    {
+        if !client.label_exists(graph, Self::LABEL)? {
+            match Self::KIND {
+                LabelKind::Vertex => client.create_vlabel(graph, Self::LABEL)?,
+                LabelKind::Edge => client.create_elabel(graph, Self::LABEL)?,
+            };
+        }
+
+        let existing = client.list_constraints(graph, Self::LABEL)?;
+        for constraint in Self::constraints() {
+            if existing.iter().all(|x| x.name != constraint.name()) {
+                client.create_constraint(graph, Self::LABEL, &constraint)?;
+            }
+        }
+
+        let existing = client.list_indexes(graph, Self::LABEL)?;
+        for index in Self::indexes() {
+            if existing.iter().all(|x| x.name != index.name()) {
+                client.create_index(graph, Self::LABEL, &index)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Async version of the [`Label::ensure_schema`]
+    #[cfg(feature = "tokio")]
+    fn ensure_schema_async<'a, C>(
+        client: &'a C,
+        graph: &'a str,
+    ) -> futures_core::future::BoxFuture<'a, Result<(), Error>>
+    where
+        Self: Sized,
+        C: crate::tokio::AgeClient + Sync,
+    {
+        Box::pin(async move {
+            if !client.label_exists(graph, Self::LABEL).await? {
+                match Self::KIND {
+                    LabelKind::Vertex => client.create_vlabel(graph, Self::LABEL).await?,
+                    LabelKind::Edge => client.create_elabel(graph, Self::LABEL).await?,
+                };
+            }
+
+            let existing = client.list_constraints(graph, Self::LABEL).await?;
+            for constraint in Self::constraints() {
+                if existing.iter().all(|x| x.name != constraint.name()) {
+                    client
+                        .create_constraint(graph, Self::LABEL, &constraint)
+                        .await?;
+                }
+            }
+
+            let existing = client.list_indexes(graph, Self::LABEL).await?;
+            for index in Self::indexes() {
+                if existing.iter().all(|x| x.name != index.name()) {
+                    client.create_index(graph, Self::LABEL, &index).await?;
+                }
+            }
+
+            Ok(())
+        })
+    }
+}
+
+/// Name of the index or constraint of the label property (`<label>_<property>_<suffix>`).
+///
+/// Postgres silently truncates identifiers longer than 63 bytes, which would break the name
+/// checks of `ensure_schema`. Such names are truncated here instead and end with the hash
+/// of the full name, so they are stable between the runs and distinct for every property
+fn schema_name(label: &str, property: &str, suffix: &str) -> String {
+    let name = format!("{}_{}_{}", label, property, suffix);
+    if name.len() <= MAX_IDENTIFIER_LEN {
+        return name;
+    }
+
+    // FNV-1a, as the std hashers are not guaranteed to be stable
+    let hash = name.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    let hash = format!("_{:016x}", hash);
+
+    let mut end = MAX_IDENTIFIER_LEN - hash.len();
+    while !name.is_char_boundary(end) {
+        end -= 1;
+    }
+
+    format!("{}{}", &name[..end], hash)
+}
@@ -10,11 +10,23 @@
 //!
 //! * pure cypher query and execution
 //! * field constraints creation
+//! * typed property constraints ([`Constraint`]), listing and drop
 //! * (unique) indexes creation
+//! * property, composite, GIN and edge indexes ([`Index`]), listing and drop
 //! * graph creation / drop
-//! * graph existance checks
+//! * graph existance checks and catalog introspection
+//! * vertex and edge labels management
 //! * postgres/tokio-postgres client usage
 //! * query fields builders
+//! * dynamic agtype values ([`AgValue`])
+//! * typed rows ([`FromAgRow`])
+//! * structs bound to the labels with their schema ([`Label`])
+//! * lazily decoded, streamed results
+//! * batched vertex inserts (`create_vertices`)
+//! * named, validated query parameters ([`CypherParams`], [`params!`])
+//! * transactions and nested savepoints (`with_graph_transaction`)
+//! * configurable session setup ([`AgeConfig`])
+//! * versioned graph schema migrations ([`Migrations`])
 //!
 //! ## Sync and async client
 //!
@@ -36,10 +48,31 @@
 //! | sync        | `postgres` based client                         | true    |
 //! | tokio       | `tokio-postgres based client                    | true    |
 //! | serializers | serializers that can be used for query building | false   |
+//! | bigdecimal  | `::numeric` conversions into `BigDecimal`       | false   |
+//! | derive      | `#[derive(FromAgRow)]`, `#[derive(AgeLabel)]`   | false   |
+//! | deadpool    | `deadpool-postgres` pool setup                  | false   |
+//! | bb8         | `bb8` connection manager                        | false   |
+//! | r2d2        | `r2d2` connection manager                       | false   |
+//! | tracing     | `tracing` events for connection failures        | false   |
 
 #[macro_use]
 mod constants;
 mod age_types;
+pub mod agtype;
+mod bulk;
+mod catalog;
+mod columns;
+mod config;
+mod constraint;
+mod error;
+mod graph_id;
+mod identifier;
+mod index;
+mod label;
+mod migrate;
+mod params;
+mod row;
+mod value;
 
 /// Used for synchronous age connection. Requires `sync` feature
 #[cfg(feature = "sync")]
@@ -49,9 +82,36 @@ pub mod sync;
 #[cfg(feature = "tokio")]
 pub mod tokio;
 
+/// Connection pools with AGE setup. Requires `deadpool`, `bb8` or `r2d2` feature
+#[cfg(any(feature = "deadpool", feature = "bb8", feature = "r2d2"))]
+pub mod pool;
+
 /// Used for query builing . Requires `serializers` feature
 #[cfg(feature = "serializers")]
 pub mod serializers;
 
 pub use age_types::{AgType, Edge, Path, Vertex};
+pub use agtype::DecodeError;
+pub use bulk::DEFAULT_INSERT_BATCH_SIZE;
+pub use catalog::{CountMethod, GraphInfo, GraphStats, LabelInfo, LabelKind, LabelStats};
+pub use columns::return_columns;
+pub use config::{AgeConfig, ConnectionErrorCallback, SearchPath};
+pub use constraint::{Constraint, ConstraintInfo, PropertyType};
+pub use error::Error;
+pub use graph_id::GraphId;
+pub use identifier::{GraphName, LabelName};
+pub use index::{Index, IndexInfo, IndexKey, IndexMethod};
+pub use label::Label;
+pub use migrate::{AppliedMigration, Migration, MigrationDirection, MigrationStep, Migrations};
+pub use params::CypherParams;
 pub use postgres::NoTls;
+pub use row::{FromAgRow, Row};
+pub use value::{AgMap, AgValue, Numeric};
+
+/// Derives [`FromAgRow`] for structs. Requires `derive` feature
+#[cfg(feature = "derive")]
+pub use apache_age_derive::FromAgRow;
+
+/// Derives [`Label`] for structs. Requires `derive` feature
+#[cfg(feature = "derive")]
+pub use apache_age_derive::AgeLabel;
@@ -0,0 +1,307 @@
+use crate::error::Error;
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::time::SystemTime;
+
+/// Single step of the migration. `F` is the closure type of the client
+/// (`sync::MigrationFn` or `tokio::MigrationFn`)
+pub enum MigrationStep<F> {
+    /// Cypher statements separated with `;`, executed against the migrated graph
+    Cypher(String),
+    /// Sql statements executed as a batch
+    Sql(String),
+    /// Closure receiving the transaction and the graph name
+    Rust(F),
+}
+
+impl<F> MigrationStep<F> {
+    pub fn cypher(cypher: impl Into<String>) -> Self {
+        MigrationStep::Cypher(cypher.into())
+    }
+
+    pub fn sql(sql: impl Into<String>) -> Self {
+        MigrationStep::Sql(sql.into())
+    }
+}
+
+/// Versioned change of the graph schema with the optional way back
+pub struct Migration<F> {
+    version: i64,
+    name: String,
+    up: MigrationStep<F>,
+    down: Option<MigrationStep<F>>,
+}
+
+impl<F> Migration<F> {
+    pub fn new(version: i64, name: impl Into<String>, up: MigrationStep<F>) -> Self {
+        Self {
+            version,
+            name: name.into(),
+            up,
+            down: None,
+        }
+    }
+
+    pub fn cypher(version: i64, name: impl Into<String>, up: impl Into<String>) -> Self {
+        Self::new(version, name, MigrationStep::cypher(up))
+    }
+
+    pub fn sql(version: i64, name: impl Into<String>, up: impl Into<String>) -> Self {
+        Self::new(version, name, MigrationStep::sql(up))
+    }
+
+    /// Step reverting the migration. Migrations without it cannot be reverted
+    pub fn down(mut self, down: MigrationStep<F>) -> Self {
+        self.down = Some(down);
+        self
+    }
+
+    pub fn version(&self) -> i64 {
+        self.version
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn up_step(&self) -> &MigrationStep<F> {
+        &self.up
+    }
+
+    pub fn down_step(&self) -> Option<&MigrationStep<F>> {
+        self.down.as_ref()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MigrationDirection {
+    Up,
+    Down,
+}
+
+/// Migration recorded in the bookkeeping table
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AppliedMigration {
+    pub version: i64,
+    pub name: String,
+    pub applied_at: SystemTime,
+}
+
+impl AppliedMigration {
+    /// Reads the `version, name, applied_at` row of the bookkeeping table
+    #[cfg(any(feature = "sync", feature = "tokio"))]
+    pub(crate) fn from_row(row: &postgres::Row) -> Result<Self, Error> {
+        Ok(Self {
+            version: row.try_get(0)?,
+            name: row.try_get(1)?,
+            applied_at: row.try_get(2)?,
+        })
+    }
+}
+
+/// Ordered set of migrations applied with `migrate` (`migrate_to`) of the clients.
+///
+/// Applied versions are recorded per graph in the `public.age_migrations` table.
+/// Every run takes a transaction-level advisory lock of the graph and is executed
+/// in a single transaction, so concurrent runs wait for each other and failed runs
+/// leave nothing behind. Missing graph is created first.
+///
+/// ```
+/// use apache_age::sync::{AgeClient, Migration, MigrationStep, Migrations};
+///
+/// let migrations = Migrations::new()
+///     .migration(
+///         Migration::sql(1, "labels", "SELECT create_vlabel('social', 'Person')")
+///             .down(MigrationStep::sql("SELECT drop_label('social', 'Person')")),
+///     )
+///     .migration(Migration::new(
+///         2,
+///         "person_email",
+///         MigrationStep::rust(|tx, graph| {
+///             tx.unique_index(graph, "Person", "person_email_idx", "email")?;
+///             Ok(())
+///         }),
+///     ))
+///     .migration(Migration::cypher(3, "admin", "CREATE (:Person {email: 'admin@example.com'})"));
+///
+/// let plan = migrations.plan(&[1], None).unwrap();
+/// assert_eq!(plan.iter().map(|(_, x)| x.version()).collect::<Vec<_>>(), [2, 3]);
+/// ```
+pub struct Migrations<F> {
+    migrations: BTreeMap<i64, Migration<F>>,
+    error: Option<String>,
+}
+
+impl<F> Default for Migrations<F> {
+    fn default() -> Self {
+        Self {
+            migrations: BTreeMap::new(),
+            error: None,
+        }
+    }
+}
+
+impl<F> Migrations<F> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the migrations from the files named `<version>_<name>.<up|down>.<cypher|sql>`,
+    /// e.g. `0001_people.up.cypher` and `0001_people.down.cypher`. Other files are skipped
+    pub fn from_dir(dir: impl AsRef<Path>) -> Result<Self, Error> {
+        let dir = dir.as_ref();
+        let read_error = |e: std::io::Error| Error::Migration(format!("{}: {}", dir.display(), e));
+
+        let mut paths = vec![];
+        for entry in std::fs::read_dir(dir).map_err(read_error)? {
+            paths.push(entry.map_err(read_error)?.path());
+        }
+        paths.sort();
+
+        let mut downs = vec![];
+        let mut migrations = Self::new();
+        for path in paths {
+            let Some(file_name) = path.file_name().and_then(|x| x.to_str()) else {
+                continue;
+            };
+            let Some((stem, kind)) = file_name.rsplit_once('.') else {
+                continue;
+            };
+            if kind != "cypher" && kind != "sql" {
+                continue;
+            }
+
+            let invalid =
+                || Error::Migration(format!("Invalid migration file name: {}", file_name));
+            let (stem, direction) = stem.rsplit_once('.').ok_or_else(invalid)?;
+            let (version, name) = stem.split_once('_').ok_or_else(invalid)?;
+            let version: i64 = version.parse().map_err(|_| invalid())?;
+
+            let text = std::fs::read_to_string(&path)
+                .map_err(|e| Error::Migration(format!("{}: {}", path.display(), e)))?;
+            let step = match kind {
+                "cypher" => MigrationStep::Cypher(text),
+                _ => MigrationStep::Sql(text),
+            };
+
+            match direction {
+                "up" => migrations = migrations.migration(Migration::new(version, name, step)),
+                "down" => downs.push((version, step)),
+                _ => return Err(invalid()),
+            }
+        }
+
+        for (version, step) in downs {
+            let migration = migrations
+                .migrations
+                .get_mut(&version)
+                .ok_or_else(|| Error::Migration(format!("Migration {} has no up step", version)))?;
+            if migration.down.replace(step).is_some() {
+                return Err(Error::Migration(format!(
+                    "Migration {} has multiple down steps",
+                    version
+                )));
+            }
+        }
+
+        migrations.check()?;
+        Ok(migrations)
+    }
+
+    /// Adds a migration. Duplicated versions are reported when migrations are planned
+    pub fn migration(mut self, migration: Migration<F>) -> Self {
+        match self.migrations.entry(migration.version) {
+            Entry::Vacant(entry) => {
+                entry.insert(migration);
+            }
+            Entry::Occupied(entry) => {
+                self.error
+                    .get_or_insert(format!("Duplicated migration version {}", entry.key()));
+            }
+        }
+        self
+    }
+
+    /// Migrations in the order of versions
+    pub fn iter(&self) -> impl Iterator<Item = &Migration<F>> {
+        self.migrations.values()
+    }
+
+    pub fn latest_version(&self) -> Option<i64> {
+        self.migrations.keys().next_back().copied()
+    }
+
+    /// Migrations to apply (in the order of versions) or to revert (in the reverse order),
+    /// so only `target` version and below are applied. `None` targets the latest version
+    pub fn plan(
+        &self,
+        applied: &[i64],
+        target: Option<i64>,
+    ) -> Result<Vec<(MigrationDirection, &Migration<F>)>, Error> {
+        self.check()?;
+
+        let applied: BTreeSet<i64> = applied.iter().copied().collect();
+        if let Some(unknown) = applied.iter().find(|x| !self.migrations.contains_key(x)) {
+            return Err(Error::Migration(format!(
+                "Applied migration {} is unknown",
+                unknown
+            )));
+        }
+        let target = target.or(self.latest_version()).unwrap_or(i64::MIN);
+
+        let mut plan = vec![];
+        for migration in self.migrations.values().rev() {
+            if migration.version > target && applied.contains(&migration.version) {
+                if migration.down.is_none() {
+                    return Err(Error::Migration(format!(
+                        "Migration {} cannot be reverted",
+                        migration.version
+                    )));
+                }
+                plan.push((MigrationDirection::Down, migration));
+            }
+        }
+        for migration in self.migrations.values() {
+            if migration.version <= target && !applied.contains(&migration.version) {
+                plan.push((MigrationDirection::Up, migration));
+            }
+        }
+
+        Ok(plan)
+    }
+
+    fn check(&self) -> Result<(), Error> {
+        match &self.error {
+            Some(e) => Err(Error::Migration(e.clone())),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Key of the advisory lock taken by the migrations of the graph
+#[cfg(any(feature = "sync", feature = "tokio"))]
+pub(crate) fn lock_key(graph: &str) -> String {
+    format!("age_migrations:{}", graph)
+}
+
+/// Cypher statements separated with `;` (outside of the strings and comments)
+#[cfg(any(feature = "sync", feature = "tokio"))]
+pub(crate) fn cypher_statements(cypher: &str) -> Vec<&str> {
+    let mask = crate::columns::mask_literals(cypher, true);
+    let mut statements = vec![];
+    let mut begin = 0;
+
+    for end in mask
+        .match_indices(';')
+        .map(|(i, _)| i)
+        .chain([cypher.len()])
+    {
+        if !mask[begin..end].trim().is_empty() {
+            statements.push(cypher[begin..end].trim());
+        }
+        begin = end + 1;
+    }
+
+    statements
+}
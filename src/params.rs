@@ -0,0 +1,174 @@
+use crate::bulk::to_value;
+use crate::columns::mask_literals;
+use crate::error::Error;
+use bytes::BufMut;
+use postgres_types::{to_sql_checked, IsNull, ToSql, Type};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+
+/// Named parameters of the cypher query, passed as a single agtype map.
+///
+/// Keys are checked against the `$placeholders` of the query before it is sent,
+/// so typos are reported as [`Error::InvalidParameters`] instead of silent nulls.
+///
+/// ```
+/// use apache_age::{params, CypherParams};
+///
+/// let params = params! { name: "John", age: 42 };
+/// assert!(params.validate("CREATE (n: Person {name: $name, age: $age})").is_ok());
+/// assert!(params.validate("CREATE (n: Person {name: $nmae})").is_err());
+///
+/// let params = CypherParams::new().param("name", "John");
+/// assert!(params.validate("MATCH (n {name: $name}) RETURN n").is_ok());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CypherParams {
+    values: Map<String, Value>,
+    error: Option<String>,
+}
+
+impl CypherParams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds parameters out of any value serialized as a map (e.g. struct).
+    /// Other values are rejected, as AGE accepts only a map of parameters
+    pub fn from_serialize<T: Serialize + ?Sized>(value: &T) -> Result<Self, Error> {
+        match to_value(value) {
+            Ok(Value::Object(values)) => Ok(Self {
+                values,
+                error: None,
+            }),
+            Ok(_) => Err(Error::Serialization(
+                "Cypher parameters have to be serialized as a map".to_string(),
+            )),
+            Err(e) => Err(Error::Serialization(e.to_string())),
+        }
+    }
+
+    /// Adds a parameter. Serialization errors (including NaN and infinite floats)
+    /// are reported, when parameters are validated
+    pub fn param<T: Serialize>(mut self, key: impl Into<String>, value: T) -> Self {
+        match to_value(&value) {
+            Ok(value) => {
+                self.values.insert(key.into(), value);
+            }
+            Err(e) => {
+                self.error.get_or_insert(format!("{}: {}", key.into(), e));
+            }
+        }
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.values.keys().map(String::as_str)
+    }
+
+    /// Checks that every `$placeholder` of the query has a value and every value is used
+    pub fn validate(&self, cypher: &str) -> Result<(), Error> {
+        if let Some(e) = &self.error {
+            return Err(Error::Serialization(e.clone()));
+        }
+
+        let placeholders = placeholders(cypher);
+        let missing: Vec<String> = placeholders
+            .iter()
+            .filter(|x| !self.values.contains_key(**x))
+            .map(|x| x.to_string())
+            .collect();
+        let unused: Vec<String> = self
+            .values
+            .keys()
+            .filter(|x| !placeholders.contains(x.as_str()))
+            .cloned()
+            .collect();
+
+        if missing.is_empty() && unused.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::InvalidParameters { missing, unused })
+        }
+    }
+}
+
+impl Serialize for CypherParams {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.values.serialize(serializer)
+    }
+}
+
+/// Parameters are sent as an agtype map
+impl ToSql for CypherParams {
+    fn accepts(ty: &Type) -> bool {
+        ty.schema() == "ag_catalog" && ty.name() == "agtype"
+    }
+
+    fn to_sql(
+        &self,
+        _ty: &Type,
+        out: &mut postgres_types::private::BytesMut,
+    ) -> Result<postgres_types::IsNull, Box<dyn std::error::Error + Sync + Send>>
+    where
+        Self: Sized,
+    {
+        if let Some(e) = &self.error {
+            return Err(Error::Serialization(e.clone()).into());
+        }
+
+        out.put_u8(1);
+        serde_json::ser::to_writer(out.writer(), &self.values)?;
+        Ok(IsNull::No)
+    }
+
+    to_sql_checked!();
+}
+
+/// Names of the `$placeholders` used within the cypher query
+pub(crate) fn placeholders(cypher: &str) -> BTreeSet<&str> {
+    let mask = mask_literals(cypher, true);
+    let bytes = mask.as_bytes();
+    let mut placeholders = BTreeSet::new();
+
+    for (i, _) in mask.match_indices('$') {
+        let begin = i + 1;
+        let mut end = begin;
+        while end < bytes.len() && (bytes[end].is_ascii_alphanumeric() || bytes[end] == b'_') {
+            end += 1;
+        }
+        if end > begin && !bytes[begin].is_ascii_digit() {
+            placeholders.insert(&cypher[begin..end]);
+        }
+    }
+
+    placeholders
+}
+
+/// Builds [`CypherParams`] out of `key: value` pairs
+///
+/// ```
+/// use apache_age::params;
+///
+/// let params = params! {
+///     name: "John",
+///     tags: vec!["a", "b"],
+/// };
+/// assert_eq!(params.keys().collect::<Vec<_>>(), ["name", "tags"]);
+/// ```
+#[macro_export]
+macro_rules! params {
+    () => {
+        $crate::CypherParams::new()
+    };
+    ($($key:ident : $value:expr),+ $(,)?) => {
+        $crate::CypherParams::new()$(.param(stringify!($key), $value))+
+    };
+}
@@ -0,0 +1,98 @@
+//! [`bb8`](https://docs.rs/bb8) integration
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! use apache_age::pool::bb8::AgeConnectionManager;
+//! use apache_age::tokio::AgeClient;
+//! use apache_age::NoTls;
+//!
+//! let manager =
+//!     AgeConnectionManager::new("host=localhost user=postgres password=passwd port=8081", NoTls)?;
+//! let pool = bb8::Pool::builder().max_size(16).build(manager).await?;
+//!
+//! let client = pool.get().await?;
+//! client.create_graph("my_graph").await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::config::{AgeConfig, ConnectionErrorCallback};
+use crate::error::Error;
+use crate::tokio::spawn_connection;
+use std::fmt;
+use tokio_postgres::{
+    tls::{MakeTlsConnect, TlsConnect},
+    Client, Config, Socket,
+};
+
+/// Connection manager, which sets up the AGE session on every new connection.
+///
+/// [`AgeConfig::session_query`] is run again whenever the connection is validated,
+/// so the session survives `RESET ALL` or `DISCARD ALL` run by the previous user
+#[derive(Clone)]
+pub struct AgeConnectionManager<T> {
+    config: Config,
+    tls: T,
+    setup: String,
+    session: String,
+    on_connection_error: Option<ConnectionErrorCallback>,
+}
+
+impl<T> AgeConnectionManager<T>
+where
+    T: MakeTlsConnect<Socket> + Clone + Send + Sync + 'static,
+{
+    pub fn new(config: impl Into<AgeConfig>, tls: T) -> Result<Self, Error> {
+        let config = config.into();
+
+        Ok(Self {
+            config: config.pg_config()?,
+            tls,
+            setup: config.setup_query(),
+            session: config.session_query(),
+            on_connection_error: config.connection_error_callback(),
+        })
+    }
+}
+
+impl<T> fmt::Debug for AgeConnectionManager<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AgeConnectionManager")
+            .field("config", &self.config)
+            .field("setup", &self.setup)
+            .field("session", &self.session)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> ::bb8::ManageConnection for AgeConnectionManager<T>
+where
+    T: MakeTlsConnect<Socket> + Clone + Send + Sync + 'static,
+    T::Stream: Send + Sync,
+    T::TlsConnect: Send + Sync,
+    <T::TlsConnect as TlsConnect<Socket>>::Future: Send,
+{
+    type Connection = Client;
+    type Error = Error;
+
+    async fn connect(&self) -> Result<Client, Error> {
+        let (client, connection) = self
+            .config
+            .connect(self.tls.clone())
+            .await
+            .map_err(Error::Connection)?;
+
+        spawn_connection(connection, self.on_connection_error.clone());
+
+        client.batch_execute(&self.setup).await?;
+        Ok(client)
+    }
+
+    async fn is_valid(&self, client: &mut Client) -> Result<(), Error> {
+        Ok(client.batch_execute(&self.session).await?)
+    }
+
+    fn has_broken(&self, client: &mut Client) -> bool {
+        client.is_closed()
+    }
+}
@@ -0,0 +1,85 @@
+//! [`deadpool-postgres`](https://docs.rs/deadpool-postgres) integration
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! use apache_age::pool::deadpool::{pool_builder, ManagerConfig};
+//! use apache_age::tokio::AgeClient;
+//! use apache_age::NoTls;
+//!
+//! let config = "host=localhost user=postgres password=passwd port=8081";
+//! let pool = pool_builder(config, NoTls, ManagerConfig::default())?
+//!     .max_size(16)
+//!     .build()?;
+//!
+//! let client = pool.get().await?;
+//! client.create_graph("my_graph").await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::config::AgeConfig;
+use crate::error::Error;
+use deadpool_postgres::HookError;
+use tokio_postgres::{
+    tls::{MakeTlsConnect, TlsConnect},
+    Socket,
+};
+
+pub use deadpool_postgres::{Hook, Manager, ManagerConfig, Pool, PoolBuilder, RecyclingMethod};
+
+/// Pool builder with the AGE session set up on every new connection
+/// and again after the connection is recycled (see [`recycling_method`])
+pub fn pool_builder<P, T>(
+    config: P,
+    tls: T,
+    manager_config: ManagerConfig,
+) -> Result<PoolBuilder, Error>
+where
+    P: Into<AgeConfig>,
+    T: MakeTlsConnect<Socket> + Clone + Sync + Send + 'static,
+    T::Stream: Sync + Send,
+    T::TlsConnect: Sync + Send,
+    <T::TlsConnect as TlsConnect<Socket>>::Future: Send,
+{
+    let config = config.into();
+    let manager_config = ManagerConfig {
+        recycling_method: recycling_method(manager_config.recycling_method, &config),
+    };
+    let manager = Manager::from_config(config.pg_config()?, tls, manager_config);
+
+    Ok(Pool::builder(manager).post_create(post_create_hook(&config)))
+}
+
+/// Hook running the [`AgeConfig::setup_query`] on every new connection
+pub fn post_create_hook(config: &AgeConfig) -> Hook {
+    let setup = config.setup_query();
+
+    Hook::async_fn(move |client, _| {
+        let setup = setup.clone();
+        Box::pin(async move {
+            client
+                .batch_execute(&setup)
+                .await
+                .map_err(HookError::Backend)
+        })
+    })
+}
+
+/// Extends the recycling method with the [`AgeConfig::session_query`],
+/// so the session is set up again after it is reset.
+///
+/// [`RecyclingMethod::Fast`] is kept as is, since it does not touch the session
+pub fn recycling_method(method: RecyclingMethod, config: &AgeConfig) -> RecyclingMethod {
+    let session = config.session_query();
+
+    match method.query() {
+        None => method,
+        Some(_) if session.is_empty() => method,
+        Some("") => RecyclingMethod::Custom(format!("{};", session)),
+        Some(sql) => {
+            let sql = sql.trim_end();
+            let separator = if sql.ends_with(';') { "" } else { ";" };
+            RecyclingMethod::Custom(format!("{}{} {};", sql, separator, session))
+        }
+    }
+}
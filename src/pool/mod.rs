@@ -0,0 +1,15 @@
+//! Connection pools, which set up AGE (`LOAD 'age'` and `search_path`) on every pooled connection
+//!
+//! * `deadpool` (`deadpool` feature) - post create hook and recycling method for `deadpool-postgres`
+//! * `bb8` (`bb8` feature) - async connection manager
+//! * `r2d2` (`r2d2` feature) - sync connection manager
+//!
+//! Session is set up according to [`AgeConfig`](crate::AgeConfig). Pooled connections dereference to the `postgres`/`tokio-postgres` clients,
+//! so they can be used with the `AgeClient` traits directly
+
+#[cfg(feature = "bb8")]
+pub mod bb8;
+#[cfg(feature = "deadpool")]
+pub mod deadpool;
+#[cfg(feature = "r2d2")]
+pub mod r2d2;
@@ -0,0 +1,79 @@
+//! [`r2d2`](https://docs.rs/r2d2) integration
+//!
+//! ```no_run
+//! use apache_age::pool::r2d2::AgeConnectionManager;
+//! use apache_age::sync::AgeClient;
+//! use apache_age::NoTls;
+//!
+//! let manager =
+//!     AgeConnectionManager::new("host=localhost user=postgres password=passwd port=8081", NoTls)?;
+//! let pool = r2d2::Pool::builder().max_size(16).build(manager)?;
+//!
+//! let mut client = pool.get()?;
+//! client.create_graph("my_graph")?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use crate::config::AgeConfig;
+use crate::error::Error;
+use postgres::{
+    tls::{MakeTlsConnect, TlsConnect},
+    Client, Config, Socket,
+};
+
+/// Connection manager, which sets up the AGE session on every new connection.
+///
+/// [`AgeConfig::session_query`] is run again whenever the connection is validated,
+/// so the session survives `RESET ALL` or `DISCARD ALL` run by the previous user
+#[derive(Debug, Clone)]
+pub struct AgeConnectionManager<T> {
+    config: Config,
+    tls: T,
+    setup: String,
+    session: String,
+}
+
+impl<T> AgeConnectionManager<T>
+where
+    T: MakeTlsConnect<Socket> + Clone + Send + Sync + 'static,
+{
+    pub fn new(config: impl Into<AgeConfig>, tls: T) -> Result<Self, Error> {
+        let config = config.into();
+
+        Ok(Self {
+            config: config.pg_config()?.into(),
+            tls,
+            setup: config.setup_query(),
+            session: config.session_query(),
+        })
+    }
+}
+
+impl<T> ::r2d2::ManageConnection for AgeConnectionManager<T>
+where
+    T: MakeTlsConnect<Socket> + Clone + Send + Sync + 'static,
+    T::TlsConnect: Send,
+    T::Stream: Send,
+    <T::TlsConnect as TlsConnect<Socket>>::Future: Send,
+{
+    type Connection = Client;
+    type Error = Error;
+
+    fn connect(&self) -> Result<Client, Error> {
+        let mut client = self
+            .config
+            .connect(self.tls.clone())
+            .map_err(Error::Connection)?;
+
+        client.batch_execute(&self.setup)?;
+        Ok(client)
+    }
+
+    fn is_valid(&self, client: &mut Client) -> Result<(), Error> {
+        Ok(client.batch_execute(&self.session)?)
+    }
+
+    fn has_broken(&self, client: &mut Client) -> bool {
+        client.is_closed()
+    }
+}
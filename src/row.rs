@@ -0,0 +1,78 @@
+use crate::age_types::{AgType, Edge, Path, Vertex};
+use crate::error::Error;
+use crate::graph_id::GraphId;
+use crate::value::AgValue;
+use postgres_types::FromSql;
+
+pub use postgres::Row;
+
+/// Decodes a typed value out of the cypher query row.
+///
+/// * single values ([`AgType`], [`Vertex`], [`Edge`], [`Path`], [`AgValue`]) are read from the first column
+/// * tuples are read column by column (`RETURN a, b, count(*)` into `(Vertex<A>, Vertex<B>, AgType<i64>)`)
+/// * structs can derive it with the `derive` feature. Fields are read from the columns with the same
+///   name (see [`return_columns`](crate::return_columns)), `#[age(rename = "...")]` overrides the column name
+///
+/// ```ignore
+/// #[derive(FromAgRow)]
+/// struct PersonWithFriends {
+///     person: Vertex<Person>,
+///     #[age(rename = "count(f)")]
+///     friends: AgType<i64>,
+/// }
+///
+/// // MATCH (person: Person)-[:Knows]->(f) RETURN person, count(f)
+/// ```
+pub trait FromAgRow: Sized {
+    fn from_ag_row(row: &Row) -> Result<Self, Error>;
+}
+
+macro_rules! from_ag_row_single {
+    ($( [$($param:ident),*] $ty:ty ),*) => {
+        $(
+            impl<$($param),*> FromAgRow for $ty
+            where
+                $ty: for<'a> FromSql<'a>,
+            {
+                fn from_ag_row(row: &Row) -> Result<Self, Error> {
+                    Ok(row.try_get(0)?)
+                }
+            }
+        )*
+    };
+}
+
+from_ag_row_single! {
+    [T] AgType<T>,
+    [T] Vertex<T>,
+    [T] Edge<T>,
+    [V, E] Path<V, E>,
+    [] AgValue,
+    [] GraphId
+}
+
+macro_rules! from_ag_row_tuple {
+    ($( ($($param:ident $index:tt),+) )*) => {
+        $(
+            impl<$($param),+> FromAgRow for ($($param,)+)
+            where
+                $($param: for<'a> FromSql<'a>,)+
+            {
+                fn from_ag_row(row: &Row) -> Result<Self, Error> {
+                    Ok(($(row.try_get::<_, $param>($index)?,)+))
+                }
+            }
+        )*
+    };
+}
+
+from_ag_row_tuple! {
+    (A 0)
+    (A 0, B 1)
+    (A 0, B 1, C 2)
+    (A 0, B 1, C 2, D 3)
+    (A 0, B 1, C 2, D 3, E 4)
+    (A 0, B 1, C 2, D 3, E 4, F 5)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7)
+}
@@ -1,6 +1,6 @@
 use serde::{ser, Serialize};
 
-use super::SerializationError;
+use crate::error::Error;
 
 struct Serializer {
     // This string starts empty and JSON is appended as values are serialized.
@@ -13,7 +13,7 @@ pub fn to_string<T>(
     value: &T,
     variable: String,
     fields: Option<Vec<String>>,
-) -> Result<String, SerializationError>
+) -> Result<String, Error>
 where
     T: Serialize,
 {
@@ -28,7 +28,7 @@ where
 
 impl<'a> ser::Serializer for &'a mut Serializer {
     type Ok = ();
-    type Error = SerializationError;
+    type Error = Error;
 
     type SerializeSeq = Self;
 
@@ -204,7 +204,7 @@ impl<'a> ser::Serializer for &'a mut Serializer {
 impl<'a> ser::SerializeStruct for &'a mut Serializer {
     type Ok = ();
 
-    type Error = SerializationError;
+    type Error = Error;
 
     fn serialize_field<T: ?Sized>(
         &mut self,
@@ -235,7 +235,7 @@ impl<'a> ser::SerializeStruct for &'a mut Serializer {
 impl<'a> ser::SerializeStructVariant for &'a mut Serializer {
     type Ok = ();
 
-    type Error = SerializationError;
+    type Error = Error;
 
     fn serialize_field<T: ?Sized>(
         &mut self,
@@ -256,7 +256,7 @@ impl<'a> ser::SerializeStructVariant for &'a mut Serializer {
 impl<'a> ser::SerializeTuple for &'a mut Serializer {
     type Ok = ();
 
-    type Error = SerializationError;
+    type Error = Error;
 
     fn serialize_element<T: ?Sized>(&mut self, _value: &T) -> Result<(), Self::Error>
     where
@@ -273,7 +273,7 @@ impl<'a> ser::SerializeTuple for &'a mut Serializer {
 impl<'a> ser::SerializeTupleStruct for &'a mut Serializer {
     type Ok = ();
 
-    type Error = SerializationError;
+    type Error = Error;
 
     fn serialize_field<T: ?Sized>(&mut self, _value: &T) -> Result<(), Self::Error>
     where
@@ -290,7 +290,7 @@ impl<'a> ser::SerializeTupleStruct for &'a mut Serializer {
 impl<'a> ser::SerializeSeq for &'a mut Serializer {
     type Ok = ();
 
-    type Error = SerializationError;
+    type Error = Error;
 
     fn serialize_element<T: ?Sized>(&mut self, _value: &T) -> Result<(), Self::Error>
     where
@@ -307,7 +307,7 @@ impl<'a> ser::SerializeSeq for &'a mut Serializer {
 impl<'a> ser::SerializeMap for &'a mut Serializer {
     type Ok = ();
 
-    type Error = SerializationError;
+    type Error = Error;
 
     fn serialize_key<T: ?Sized>(&mut self, _key: &T) -> Result<(), Self::Error>
     where
@@ -331,7 +331,7 @@ impl<'a> ser::SerializeMap for &'a mut Serializer {
 impl<'a> ser::SerializeTupleVariant for &'a mut Serializer {
     type Ok = ();
 
-    type Error = SerializationError;
+    type Error = Error;
 
     fn serialize_field<T: ?Sized>(&mut self, _value: &T) -> Result<(), Self::Error>
     where
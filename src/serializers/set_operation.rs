@@ -1,6 +1,6 @@
 use serde::{ser, Serialize};
 
-use super::SerializationError;
+use crate::error::Error;
 
 struct Serializer {
     // This string starts empty and JSON is appended as values are serialized.
@@ -15,7 +15,7 @@ pub fn to_string<T>(
     variable: String,
     input_variable: Option<String>,
     fields: Option<Vec<String>>,
-) -> Result<String, SerializationError>
+) -> Result<String, Error>
 where
     T: Serialize,
 {
@@ -31,7 +31,7 @@ where
 
 impl<'a> ser::Serializer for &'a mut Serializer {
     type Ok = ();
-    type Error = SerializationError;
+    type Error = Error;
 
     type SerializeSeq = Self;
 
@@ -207,7 +207,7 @@ impl<'a> ser::Serializer for &'a mut Serializer {
 impl<'a> ser::SerializeStruct for &'a mut Serializer {
     type Ok = ();
 
-    type Error = SerializationError;
+    type Error = Error;
 
     fn serialize_field<T: ?Sized>(
         &mut self,
@@ -243,7 +243,7 @@ impl<'a> ser::SerializeStruct for &'a mut Serializer {
 impl<'a> ser::SerializeStructVariant for &'a mut Serializer {
     type Ok = ();
 
-    type Error = SerializationError;
+    type Error = Error;
 
     fn serialize_field<T: ?Sized>(
         &mut self,
@@ -264,7 +264,7 @@ impl<'a> ser::SerializeStructVariant for &'a mut Serializer {
 impl<'a> ser::SerializeTuple for &'a mut Serializer {
     type Ok = ();
 
-    type Error = SerializationError;
+    type Error = Error;
 
     fn serialize_element<T: ?Sized>(&mut self, _value: &T) -> Result<(), Self::Error>
     where
@@ -281,7 +281,7 @@ impl<'a> ser::SerializeTuple for &'a mut Serializer {
 impl<'a> ser::SerializeTupleStruct for &'a mut Serializer {
     type Ok = ();
 
-    type Error = SerializationError;
+    type Error = Error;
 
     fn serialize_field<T: ?Sized>(&mut self, _value: &T) -> Result<(), Self::Error>
     where
@@ -298,7 +298,7 @@ impl<'a> ser::SerializeTupleStruct for &'a mut Serializer {
 impl<'a> ser::SerializeSeq for &'a mut Serializer {
     type Ok = ();
 
-    type Error = SerializationError;
+    type Error = Error;
 
     fn serialize_element<T: ?Sized>(&mut self, _value: &T) -> Result<(), Self::Error>
     where
@@ -315,7 +315,7 @@ impl<'a> ser::SerializeSeq for &'a mut Serializer {
 impl<'a> ser::SerializeMap for &'a mut Serializer {
     type Ok = ();
 
-    type Error = SerializationError;
+    type Error = Error;
 
     fn serialize_key<T: ?Sized>(&mut self, _key: &T) -> Result<(), Self::Error>
     where
@@ -339,7 +339,7 @@ impl<'a> ser::SerializeMap for &'a mut Serializer {
 impl<'a> ser::SerializeTupleVariant for &'a mut Serializer {
     type Ok = ();
 
-    type Error = SerializationError;
+    type Error = Error;
 
     fn serialize_field<T: ?Sized>(&mut self, _value: &T) -> Result<(), Self::Error>
     where
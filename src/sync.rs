@@ -1,18 +1,39 @@
 use super::constants::*;
+use crate::bulk::{vertices_cypher, RowBatches};
+use crate::catalog::{CountMethod, GraphInfo, GraphStats, LabelInfo, LabelStats};
+use crate::columns::{cypher_query, inferred_query, DEFAULT_COLUMN};
+use crate::config::AgeConfig;
+use crate::constraint::{drop_constraint_query, Constraint, ConstraintInfo};
+use crate::identifier::{label_literals, label_table, property_literal, quote_name, GraphName};
+use crate::index::{drop_index_query, Index, IndexInfo};
+use crate::migrate::{cypher_statements, lock_key, AppliedMigration, MigrationDirection};
+use crate::params::CypherParams;
+use crate::row::FromAgRow;
 
-use crate::age_types::AgType;
+use crate::age_types::{AgType, Vertex};
 use postgres::{
     tls::{MakeTlsConnect, TlsConnect},
-    Socket,
+    Portal, Row, Socket,
 };
-use serde::Serialize;
+use serde::{de::DeserializeOwned, Serialize};
+use std::marker::PhantomData;
 
-pub use postgres::{Client, Error, Statement};
+pub use crate::error::Error;
+pub use postgres::{Client, GenericClient, Statement, Transaction};
+
+/// Number of rows fetched at once by [`AgeClient::query_cypher_iter`], when no other size is requested
+pub const DEFAULT_BATCH_SIZE: i32 = 1000;
 
 /// Handles connecting, configuring and querying graph dbs within postgres instance
+///
+/// Implemented for every [`GenericClient`], so the same methods can be used
+/// on the [`Client`], [`Transaction`] and nested savepoints
 pub trait AgeClient {
-    fn connect_age<T>(params: &str, tls_mode: T) -> Result<Client, postgres::Error>
+    /// Connect and set up the AGE session. Connection string can be used in place of
+    /// [`AgeConfig`] to load `age` and set the default `search_path`
+    fn connect_age<P, T>(config: P, tls_mode: T) -> Result<Client, Error>
     where
+        P: Into<AgeConfig>,
         T: MakeTlsConnect<Socket> + 'static + Send,
         T::TlsConnect: Send,
         T::Stream: Send,
@@ -20,25 +41,33 @@ pub trait AgeClient {
 
     /// Create a new constraint for the certain label within graph
     ///
-    /// **IMPORTANT**: At least one object has to be created with a certain label
+    /// Graph and label have to be valid [`GraphName`] and [`LabelName`](crate::LabelName), `name` is quoted.
+    /// `constraint_text` is inserted as is, so it must not contain untrusted input.
+    /// Prefer [`AgeClient::create_constraint`] for the property checks
+    ///
+    /// **IMPORTANT**: Label has to exist. Create it with [`AgeClient::create_vlabel`]
+    /// ([`AgeClient::create_elabel`]) or by creating at least one object with a certain label
     fn constraint(
         &mut self,
         graph: &str,
         label: &str,
         name: &str,
         constraint_text: &str,
-    ) -> Result<u64, postgres::Error>;
+    ) -> Result<u64, Error>;
 
     /// Create unique index for the certain field for the label within graph
     ///
-    /// **IMPORTANT**: At least one object has to be created with a certain label
+    /// Graph and label have to be valid [`GraphName`] and [`LabelName`](crate::LabelName), `name` and `field` are quoted
+    ///
+    /// **IMPORTANT**: Label has to exist. Create it with [`AgeClient::create_vlabel`]
+    /// ([`AgeClient::create_elabel`]) or by creating at least one object with a certain label
     fn unique_index(
         &mut self,
         graph: &str,
         label: &str,
         name: &str,
         field: &str,
-    ) -> Result<u64, postgres::Error>;
+    ) -> Result<u64, Error>;
 
     fn required_constraint(
         &mut self,
@@ -46,11 +75,63 @@ pub trait AgeClient {
         label: &str,
         name: &str,
         field: &str,
-    ) -> Result<u64, postgres::Error>;
+    ) -> Result<u64, Error>;
+
+    /// Create constraint on the label table within graph, see [`Constraint`]
+    ///
+    /// **IMPORTANT**: Label has to exist
+    fn create_constraint(
+        &mut self,
+        graph: &str,
+        label: &str,
+        constraint: &Constraint,
+    ) -> Result<u64, Error>;
+
+    /// `CHECK` constraints of the label table
+    fn list_constraints(&mut self, graph: &str, label: &str) -> Result<Vec<ConstraintInfo>, Error>;
+
+    fn drop_constraint(&mut self, graph: &str, label: &str, name: &str) -> Result<u64, Error>;
+
+    /// Create index on the label table within graph, see [`Index`]
+    ///
+    /// **IMPORTANT**: Label has to exist
+    fn create_index(&mut self, graph: &str, label: &str, index: &Index) -> Result<u64, Error>;
+
+    /// Indexes of the label table, including the unique ones
+    fn list_indexes(&mut self, graph: &str, label: &str) -> Result<Vec<IndexInfo>, Error>;
+
+    fn drop_index(&mut self, graph: &str, name: &str) -> Result<u64, Error>;
+
+    fn create_graph(&mut self, name: &str) -> Result<u64, Error>;
+    fn drop_graph(&mut self, name: &str) -> Result<u64, Error>;
+    fn graph_exists(&mut self, name: &str) -> Result<bool, Error>;
+
+    /// Create vertex label within graph, so indexes and constraints can be set up
+    /// before any vertex exists
+    fn create_vlabel(&mut self, graph: &str, label: &str) -> Result<u64, Error>;
+
+    /// Create edge label within graph, so indexes and constraints can be set up
+    /// before any edge exists
+    fn create_elabel(&mut self, graph: &str, label: &str) -> Result<u64, Error>;
+
+    /// Drop label together with all its vertices or edges
+    fn drop_label(&mut self, graph: &str, label: &str) -> Result<u64, Error>;
+
+    fn label_exists(&mut self, graph: &str, label: &str) -> Result<bool, Error>;
+
+    /// Labels of the graph (without the default `_ag_label_vertex` and `_ag_label_edge`)
+    /// in the order of creation
+    fn list_labels(&mut self, graph: &str) -> Result<Vec<LabelInfo>, Error>;
 
-    fn create_graph(&mut self, name: &str) -> Result<u64, postgres::Error>;
-    fn drop_graph(&mut self, name: &str) -> Result<u64, postgres::Error>;
-    fn graph_exists(&mut self, name: &str) -> Result<bool, postgres::Error>;
+    /// Names of all graphs in the database
+    fn list_graphs(&mut self) -> Result<Vec<String>, Error>;
+
+    /// Graph with its labels. Returns [`Error::MissingGraph`] if it does not exist
+    fn graph_info(&mut self, name: &str) -> Result<GraphInfo, Error>;
+
+    /// Number of vertices and edges per label. Exact counts scan every label table,
+    /// use [`CountMethod::Estimate`] for large graphs
+    fn graph_stats(&mut self, name: &str, method: CountMethod) -> Result<GraphStats, Error>;
 
     /// Exexute cypher query, without any rows to be retured
     fn execute_cypher<T>(
@@ -58,7 +139,7 @@ pub trait AgeClient {
         graph: &str,
         cypher: &str,
         agtype: Option<AgType<T>>,
-    ) -> Result<u64, postgres::Error>
+    ) -> Result<u64, Error>
     where
         T: Serialize,
         T: std::fmt::Debug,
@@ -66,7 +147,8 @@ pub trait AgeClient {
 
     /// Query cypher for a single agtype (in a format of json)
     ///
-    /// **IMPORTANT**: You need to return result of the query as a map
+    /// **IMPORTANT**: You need to return result of the query as a map.
+    /// Use [`AgeClient::query_cypher_columns`] to return multiple columns
     ///
     /// Example:
     /// ```cypher
@@ -77,12 +159,203 @@ pub trait AgeClient {
         graph: &str,
         cypher: &str,
         agtype: Option<AgType<T>>,
-    ) -> Result<Vec<postgres::Row>, postgres::Error>
+    ) -> Result<Vec<postgres::Row>, Error>
+    where
+        T: Serialize,
+        T: std::fmt::Debug,
+        T: std::marker::Sync;
+
+    /// Query cypher returning multiple agtype columns
+    ///
+    /// When `columns` are not provided, they are inferred from the `RETURN` clause
+    /// (see [`return_columns`](crate::return_columns)). Columns can be accessed by their names
+    /// or positions
+    ///
+    /// Example:
+    /// ```cypher
+    /// MATCH (n: Person) RETURN n.name, n.surname, count(*) AS total
+    /// ```
+    fn query_cypher_columns<T>(
+        &mut self,
+        graph: &str,
+        cypher: &str,
+        columns: Option<&[&str]>,
+        agtype: Option<AgType<T>>,
+    ) -> Result<Vec<postgres::Row>, Error>
+    where
+        T: Serialize,
+        T: std::fmt::Debug,
+        T: std::marker::Sync;
+
+    /// Query cypher and decode every row into `R`
+    ///
+    /// Columns are inferred from the `RETURN` clause. See [`FromAgRow`] for the supported types
+    ///
+    /// ```ignore
+    /// let people: Vec<(Vertex<Person>, AgType<i64>)> = client.query_cypher_as::<_, ()>(
+    ///     "graph",
+    ///     "MATCH (n: Person)-[:Knows]->(f) RETURN n, count(f)",
+    ///     None,
+    /// )?;
+    /// ```
+    fn query_cypher_as<R, T>(
+        &mut self,
+        graph: &str,
+        cypher: &str,
+        agtype: Option<AgType<T>>,
+    ) -> Result<Vec<R>, Error>
+    where
+        R: FromAgRow,
+        T: Serialize,
+        T: std::fmt::Debug,
+        T: std::marker::Sync;
+
+    /// Query cypher for exactly one row decoded into `R`. Returns an error if the query
+    /// does not return exactly one row
+    fn query_one_cypher_as<R, T>(
+        &mut self,
+        graph: &str,
+        cypher: &str,
+        agtype: Option<AgType<T>>,
+    ) -> Result<R, Error>
+    where
+        R: FromAgRow,
+        T: Serialize,
+        T: std::fmt::Debug,
+        T: std::marker::Sync;
+
+    /// Query cypher for at most one row decoded into `R`. Returns an error if the query
+    /// returns more than one row
+    fn query_opt_cypher_as<R, T>(
+        &mut self,
+        graph: &str,
+        cypher: &str,
+        agtype: Option<AgType<T>>,
+    ) -> Result<Option<R>, Error>
+    where
+        R: FromAgRow,
+        T: Serialize,
+        T: std::fmt::Debug,
+        T: std::marker::Sync;
+
+    /// Query cypher and decode rows into `R` lazily.
+    ///
+    /// Rows are fetched through the portal in batches of `batch_size` rows
+    /// ([`DEFAULT_BATCH_SIZE`] when `None`), so the whole result is never kept in memory.
+    /// Query runs within a transaction, which is committed once all the rows are consumed
+    /// (and rolled back if the iterator is dropped earlier).
+    ///
+    /// Columns are inferred from the `RETURN` clause, same as for [`AgeClient::query_cypher_as`]
+    fn query_cypher_iter<R, T>(
+        &mut self,
+        graph: &str,
+        cypher: &str,
+        agtype: Option<AgType<T>>,
+        batch_size: Option<i32>,
+    ) -> Result<CypherIter<'_, R>, Error>
     where
+        R: FromAgRow,
         T: Serialize,
         T: std::fmt::Debug,
         T: std::marker::Sync;
 
+    /// Execute cypher query with named parameters.
+    ///
+    /// Parameters are validated against the `$placeholders` of the query before it is sent
+    /// (see [`CypherParams::validate`])
+    ///
+    /// ```ignore
+    /// client.execute_cypher_params(
+    ///     "graph",
+    ///     "CREATE (n: Person {name: $name, age: $age})",
+    ///     &params! { name: "John", age: 42 },
+    /// )?;
+    /// ```
+    fn execute_cypher_params(
+        &mut self,
+        graph: &str,
+        cypher: &str,
+        params: &CypherParams,
+    ) -> Result<u64, Error>;
+
+    /// Query cypher with named parameters and decode every row into `R`.
+    ///
+    /// Parameters are validated same as for [`AgeClient::execute_cypher_params`],
+    /// columns are inferred same as for [`AgeClient::query_cypher_as`]
+    fn query_cypher_params<R>(
+        &mut self,
+        graph: &str,
+        cypher: &str,
+        params: &CypherParams,
+    ) -> Result<Vec<R>, Error>
+    where
+        R: FromAgRow;
+
+    /// Create a vertex with the label out of every row (serialized as a map of properties).
+    ///
+    /// Rows are sent in batches of `batch_size` rows
+    /// ([`DEFAULT_INSERT_BATCH_SIZE`](crate::DEFAULT_INSERT_BATCH_SIZE) when `None`) as a single
+    /// list parameter of the `UNWIND $rows AS r CREATE (n:Label) SET n = r` query.
+    /// Every batch is a separate statement, so use [`AgeClient::with_graph_transaction`]
+    /// to create all vertices or none. Returns the number of created vertices
+    fn create_vertices<T, I>(
+        &mut self,
+        graph: &str,
+        label: &str,
+        rows: I,
+        batch_size: Option<usize>,
+    ) -> Result<u64, Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Serialize;
+
+    /// Same as [`AgeClient::create_vertices`], but returns the created vertices
+    fn create_vertices_returning<T, I>(
+        &mut self,
+        graph: &str,
+        label: &str,
+        rows: I,
+        batch_size: Option<usize>,
+    ) -> Result<Vec<Vertex<T>>, Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Serialize + DeserializeOwned;
+
+    /// Run `f` within a transaction, which is committed when `f` succeeds
+    /// and rolled back otherwise.
+    ///
+    /// Called on the transaction it creates a savepoint instead, so multi-step graph writes
+    /// can be nested
+    ///
+    /// ```ignore
+    /// client.with_graph_transaction(|tx| {
+    ///     tx.execute_cypher::<()>("graph", "CREATE (n: Person {name: 'John'})", None)?;
+    ///     tx.with_graph_transaction(|savepoint| {
+    ///         savepoint.unique_index("graph", "Person", "person_name_idx", "name")
+    ///     })?;
+    ///     Ok(())
+    /// })?;
+    /// ```
+    fn with_graph_transaction<R, F>(&mut self, f: F) -> Result<R, Error>
+    where
+        F: FnOnce(&mut Transaction<'_>) -> Result<R, Error>;
+
+    /// Apply pending migrations to the graph (created when missing).
+    /// Returns versions of the applied migrations. See [`Migrations`]
+    fn migrate(&mut self, graph: &str, migrations: &Migrations) -> Result<Vec<i64>, Error>;
+
+    /// Apply or revert migrations, so `version` becomes the latest applied one.
+    /// Returns versions of the applied (reverted) migrations in the order they were run
+    fn migrate_to(
+        &mut self,
+        graph: &str,
+        migrations: &Migrations,
+        version: i64,
+    ) -> Result<Vec<i64>, Error>;
+
+    /// Migrations applied to the graph in the order of versions
+    fn applied_migrations(&mut self, graph: &str) -> Result<Vec<AppliedMigration>, Error>;
+
     /// Prepare cypher query for future use
     /// ```
     #[doc = include_str!("../examples/prepared_statements.rs")]
@@ -92,16 +365,17 @@ pub trait AgeClient {
         graph: &str,
         cypher: &str,
         use_arg: bool,
-    ) -> Result<Statement, postgres::Error>;
+    ) -> Result<Statement, Error>;
 }
 
-impl AgeClient for Client {
-    fn create_graph(&mut self, name: &str) -> Result<u64, postgres::Error> {
-        self.execute(CREATE_GRAPH, &[&name])
+impl<C: GenericClient> AgeClient for C {
+    fn create_graph(&mut self, name: &str) -> Result<u64, Error> {
+        let name = GraphName::new(name)?;
+        Ok(self.execute(CREATE_GRAPH, &[&name.as_str()])?)
     }
 
-    fn drop_graph(&mut self, name: &str) -> Result<u64, postgres::Error> {
-        self.execute(DROP_GRAPH, &[&name])
+    fn drop_graph(&mut self, name: &str) -> Result<u64, Error> {
+        Ok(self.execute(DROP_GRAPH, &[&name])?)
     }
 
     fn execute_cypher<T>(
@@ -109,7 +383,7 @@ impl AgeClient for Client {
         graph: &str,
         cypher: &str,
         agtype: Option<AgType<T>>,
-    ) -> Result<u64, postgres::Error>
+    ) -> Result<u64, Error>
     where
         T: Serialize,
         T: std::fmt::Debug,
@@ -117,35 +391,33 @@ impl AgeClient for Client {
     {
         match agtype {
             Some(x) => {
-                let query = format!(cypher_query!(), graph, cypher, CQ_ARG);
+                let query = cypher_query(graph, cypher, true, &[DEFAULT_COLUMN])?;
 
-                self.execute(&query, &[&x])
+                Ok(self.execute(&query, &[&x])?)
             }
             None => {
-                let query = format!(cypher_query!(), graph, cypher, CQ_NO_ARG);
+                let query = cypher_query(graph, cypher, false, &[DEFAULT_COLUMN])?;
 
-                self.execute(&query, &[])
+                Ok(self.execute(&query, &[])?)
             }
         }
     }
 
-    fn connect_age<T>(params: &str, tls_mode: T) -> Result<Client, postgres::Error>
+    fn connect_age<P, T>(config: P, tls_mode: T) -> Result<Client, Error>
     where
+        P: Into<AgeConfig>,
         T: MakeTlsConnect<Socket> + 'static + Send,
         T::TlsConnect: Send,
         T::Stream: Send,
         <T::TlsConnect as TlsConnect<Socket>>::Future: Send,
     {
-        let new_connection = Client::connect(params, tls_mode);
+        let config = config.into();
+        let mut client = postgres::Config::from(config.pg_config()?)
+            .connect(tls_mode)
+            .map_err(Error::Connection)?;
 
-        if let Ok(mut client) = new_connection {
-            for query in [client.simple_query(LOAD_AGE), client.simple_query(SET_AGE)] {
-                query?;
-            }
-            Ok(client)
-        } else {
-            new_connection
-        }
+        client.batch_execute(&config.setup_query())?;
+        Ok(client)
     }
 
     fn query_cypher<T>(
@@ -153,7 +425,7 @@ impl AgeClient for Client {
         graph: &str,
         cypher: &str,
         agtype: Option<AgType<T>>,
-    ) -> Result<Vec<postgres::Row>, postgres::Error>
+    ) -> Result<Vec<postgres::Row>, Error>
     where
         T: Serialize,
         T: std::fmt::Debug,
@@ -161,28 +433,229 @@ impl AgeClient for Client {
     {
         match agtype {
             Some(x) => {
-                let query = format!(cypher_query!(), graph, cypher, CQ_ARG);
+                let query = cypher_query(graph, cypher, true, &[DEFAULT_COLUMN])?;
 
-                self.query(&query, &[&x])
+                Ok(self.query(&query, &[&x])?)
             }
             None => {
-                let query = format!(cypher_query!(), graph, cypher, CQ_NO_ARG);
+                let query = cypher_query(graph, cypher, false, &[DEFAULT_COLUMN])?;
 
-                self.query(&query, &[])
+                Ok(self.query(&query, &[])?)
             }
         }
     }
 
+    fn query_cypher_columns<T>(
+        &mut self,
+        graph: &str,
+        cypher: &str,
+        columns: Option<&[&str]>,
+        agtype: Option<AgType<T>>,
+    ) -> Result<Vec<postgres::Row>, Error>
+    where
+        T: Serialize,
+        T: std::fmt::Debug,
+        T: std::marker::Sync,
+    {
+        let query = match columns {
+            Some(columns) => cypher_query(graph, cypher, agtype.is_some(), columns)?,
+            None => inferred_query(graph, cypher, agtype.is_some())?,
+        };
+
+        match &agtype {
+            Some(x) => Ok(self.query(&query, &[x])?),
+            None => Ok(self.query(&query, &[])?),
+        }
+    }
+
+    fn query_cypher_as<R, T>(
+        &mut self,
+        graph: &str,
+        cypher: &str,
+        agtype: Option<AgType<T>>,
+    ) -> Result<Vec<R>, Error>
+    where
+        R: FromAgRow,
+        T: Serialize,
+        T: std::fmt::Debug,
+        T: std::marker::Sync,
+    {
+        let query = inferred_query(graph, cypher, agtype.is_some())?;
+        let rows = match &agtype {
+            Some(x) => self.query(&query, &[x])?,
+            None => self.query(&query, &[])?,
+        };
+
+        rows.iter().map(R::from_ag_row).collect()
+    }
+
+    fn query_one_cypher_as<R, T>(
+        &mut self,
+        graph: &str,
+        cypher: &str,
+        agtype: Option<AgType<T>>,
+    ) -> Result<R, Error>
+    where
+        R: FromAgRow,
+        T: Serialize,
+        T: std::fmt::Debug,
+        T: std::marker::Sync,
+    {
+        let query = inferred_query(graph, cypher, agtype.is_some())?;
+        let row = match &agtype {
+            Some(x) => self.query_one(&query, &[x])?,
+            None => self.query_one(&query, &[])?,
+        };
+
+        R::from_ag_row(&row)
+    }
+
+    fn query_opt_cypher_as<R, T>(
+        &mut self,
+        graph: &str,
+        cypher: &str,
+        agtype: Option<AgType<T>>,
+    ) -> Result<Option<R>, Error>
+    where
+        R: FromAgRow,
+        T: Serialize,
+        T: std::fmt::Debug,
+        T: std::marker::Sync,
+    {
+        let query = inferred_query(graph, cypher, agtype.is_some())?;
+        let row = match &agtype {
+            Some(x) => self.query_opt(&query, &[x])?,
+            None => self.query_opt(&query, &[])?,
+        };
+
+        row.as_ref().map(R::from_ag_row).transpose()
+    }
+
+    fn query_cypher_iter<R, T>(
+        &mut self,
+        graph: &str,
+        cypher: &str,
+        agtype: Option<AgType<T>>,
+        batch_size: Option<i32>,
+    ) -> Result<CypherIter<'_, R>, Error>
+    where
+        R: FromAgRow,
+        T: Serialize,
+        T: std::fmt::Debug,
+        T: std::marker::Sync,
+    {
+        let query = inferred_query(graph, cypher, agtype.is_some())?;
+        let mut transaction = self.transaction()?;
+        let portal = match &agtype {
+            Some(x) => transaction.bind(&query, &[x])?,
+            None => transaction.bind(&query, &[])?,
+        };
+
+        Ok(CypherIter {
+            transaction: Some(transaction),
+            portal,
+            batch_size: batch_size.unwrap_or(DEFAULT_BATCH_SIZE).max(1),
+            rows: Vec::new().into_iter(),
+            _marker: PhantomData,
+        })
+    }
+
+    fn execute_cypher_params(
+        &mut self,
+        graph: &str,
+        cypher: &str,
+        params: &CypherParams,
+    ) -> Result<u64, Error> {
+        params.validate(cypher)?;
+        if params.is_empty() {
+            let query = cypher_query(graph, cypher, false, &[DEFAULT_COLUMN])?;
+            Ok(self.execute(&query, &[])?)
+        } else {
+            let query = cypher_query(graph, cypher, true, &[DEFAULT_COLUMN])?;
+            Ok(self.execute(&query, &[params])?)
+        }
+    }
+
+    fn query_cypher_params<R>(
+        &mut self,
+        graph: &str,
+        cypher: &str,
+        params: &CypherParams,
+    ) -> Result<Vec<R>, Error>
+    where
+        R: FromAgRow,
+    {
+        params.validate(cypher)?;
+        let query = inferred_query(graph, cypher, !params.is_empty())?;
+        let rows = if params.is_empty() {
+            self.query(&query, &[])?
+        } else {
+            self.query(&query, &[params])?
+        };
+
+        rows.iter().map(R::from_ag_row).collect()
+    }
+
+    fn create_vertices<T, I>(
+        &mut self,
+        graph: &str,
+        label: &str,
+        rows: I,
+        batch_size: Option<usize>,
+    ) -> Result<u64, Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Serialize,
+    {
+        let cypher = vertices_cypher(label, false)?;
+        let mut created = 0;
+
+        for batch in RowBatches::new(rows, batch_size) {
+            let (count, params) = batch?;
+            self.execute_cypher_params(graph, &cypher, &params)?;
+            created += count;
+        }
+
+        Ok(created)
+    }
+
+    fn create_vertices_returning<T, I>(
+        &mut self,
+        graph: &str,
+        label: &str,
+        rows: I,
+        batch_size: Option<usize>,
+    ) -> Result<Vec<Vertex<T>>, Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Serialize + DeserializeOwned,
+    {
+        let cypher = vertices_cypher(label, true)?;
+        let mut vertices = vec![];
+
+        for batch in RowBatches::new(rows, batch_size) {
+            let (_, params) = batch?;
+            vertices.extend(self.query_cypher_params::<Vertex<T>>(graph, &cypher, &params)?);
+        }
+
+        Ok(vertices)
+    }
+
     fn constraint(
         &mut self,
         graph: &str,
         label: &str,
         name: &str,
         constraint_text: &str,
-    ) -> Result<u64, postgres::Error> {
-        let query = format!(constraint!(), graph, label, name, constraint_text);
+    ) -> Result<u64, Error> {
+        let query = format!(
+            constraint!(),
+            label_table(graph, label)?,
+            quote_name(name)?,
+            constraint_text
+        );
 
-        self.execute(&query, &[])
+        Ok(self.execute(&query, &[])?)
     }
 
     fn unique_index(
@@ -191,10 +664,15 @@ impl AgeClient for Client {
         label: &str,
         name: &str,
         field: &str,
-    ) -> Result<u64, postgres::Error> {
-        let query = format!(unique_index!(), name, graph, label, field);
+    ) -> Result<u64, Error> {
+        let query = format!(
+            unique_index!(),
+            quote_name(name)?,
+            label_table(graph, label)?,
+            property_literal(field)
+        );
 
-        self.execute(&query, &[])
+        Ok(self.execute(&query, &[])?)
     }
 
     fn required_constraint(
@@ -203,29 +681,309 @@ impl AgeClient for Client {
         label: &str,
         name: &str,
         field: &str,
-    ) -> Result<u64, postgres::Error> {
-        self.constraint(graph, label, name, &format!(required_constraint!(), field))
+    ) -> Result<u64, Error> {
+        self.constraint(
+            graph,
+            label,
+            name,
+            &format!(required_constraint!(), property_literal(field)),
+        )
+    }
+
+    fn create_constraint(
+        &mut self,
+        graph: &str,
+        label: &str,
+        constraint: &Constraint,
+    ) -> Result<u64, Error> {
+        let query = constraint.to_sql(graph, label)?;
+
+        Ok(self.execute(&query, &[])?)
+    }
+
+    fn list_constraints(&mut self, graph: &str, label: &str) -> Result<Vec<ConstraintInfo>, Error> {
+        let rows = self.query(LIST_CONSTRAINTS, &[&graph, &label])?;
+
+        rows.iter().map(ConstraintInfo::from_row).collect()
+    }
+
+    fn drop_constraint(&mut self, graph: &str, label: &str, name: &str) -> Result<u64, Error> {
+        let query = drop_constraint_query(graph, label, name)?;
+
+        Ok(self.execute(&query, &[])?)
+    }
+
+    fn create_index(&mut self, graph: &str, label: &str, index: &Index) -> Result<u64, Error> {
+        let query = index.to_sql(graph, label)?;
+
+        Ok(self.execute(&query, &[])?)
+    }
+
+    fn list_indexes(&mut self, graph: &str, label: &str) -> Result<Vec<IndexInfo>, Error> {
+        let rows = self.query(LIST_INDEXES, &[&graph, &label])?;
+
+        rows.iter().map(IndexInfo::from_row).collect()
     }
 
-    fn graph_exists(&mut self, name: &str) -> Result<bool, postgres::Error> {
+    fn drop_index(&mut self, graph: &str, name: &str) -> Result<u64, Error> {
+        let query = drop_index_query(graph, name)?;
+
+        Ok(self.execute(&query, &[])?)
+    }
+
+    fn graph_exists(&mut self, name: &str) -> Result<bool, Error> {
         match self.query(GRAPH_EXISTS, &[&name.to_string()]) {
             Ok(result) => {
                 let x: i64 = result[0].get(0);
                 Ok(x == 1)
             }
-            Err(e) => Err(e),
+            Err(e) => Err(e.into()),
         }
     }
 
+    fn create_vlabel(&mut self, graph: &str, label: &str) -> Result<u64, Error> {
+        let (graph, label) = label_literals(graph, label)?;
+        let query = format!(create_vlabel!(), graph, label);
+
+        Ok(self.execute(&query, &[])?)
+    }
+
+    fn create_elabel(&mut self, graph: &str, label: &str) -> Result<u64, Error> {
+        let (graph, label) = label_literals(graph, label)?;
+        let query = format!(create_elabel!(), graph, label);
+
+        Ok(self.execute(&query, &[])?)
+    }
+
+    fn drop_label(&mut self, graph: &str, label: &str) -> Result<u64, Error> {
+        let (graph, label) = label_literals(graph, label)?;
+        let query = format!(drop_label!(), graph, label);
+
+        Ok(self.execute(&query, &[])?)
+    }
+
+    fn label_exists(&mut self, graph: &str, label: &str) -> Result<bool, Error> {
+        let row = self.query_one(LABEL_EXISTS, &[&graph, &label])?;
+        let count: i64 = row.get(0);
+
+        Ok(count == 1)
+    }
+
+    fn list_labels(&mut self, graph: &str) -> Result<Vec<LabelInfo>, Error> {
+        let rows = self.query(LIST_LABELS, &[&graph])?;
+
+        rows.iter().map(LabelInfo::from_row).collect()
+    }
+
+    fn list_graphs(&mut self) -> Result<Vec<String>, Error> {
+        let rows = self.query(LIST_GRAPHS, &[])?;
+
+        Ok(rows
+            .iter()
+            .map(|x| x.try_get(0))
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn graph_info(&mut self, name: &str) -> Result<GraphInfo, Error> {
+        let row = self
+            .query_opt(GRAPH_INFO, &[&name])?
+            .ok_or_else(|| Error::MissingGraph(name.to_string()))?;
+        let labels = self.list_labels(name)?;
+
+        GraphInfo::from_row(&row, labels)
+    }
+
+    fn graph_stats(&mut self, name: &str, method: CountMethod) -> Result<GraphStats, Error> {
+        let rows = self.query(LABEL_STATS, &[&name])?;
+        // Every graph has the default labels
+        if rows.is_empty() {
+            return Err(Error::MissingGraph(name.to_string()));
+        }
+
+        let mut labels = rows
+            .iter()
+            .map(LabelStats::from_row)
+            .collect::<Result<Vec<_>, _>>()?;
+        if method == CountMethod::Exact {
+            for label in labels.iter_mut() {
+                let query = format!(count_label!(), label_table(name, &label.name)?);
+                label.count = self.query_one(&query, &[])?.try_get(0)?;
+            }
+        }
+
+        Ok(GraphStats { labels })
+    }
+
+    fn with_graph_transaction<R, F>(&mut self, f: F) -> Result<R, Error>
+    where
+        F: FnOnce(&mut Transaction<'_>) -> Result<R, Error>,
+    {
+        let mut transaction = self.transaction()?;
+
+        match f(&mut transaction) {
+            Ok(result) => {
+                transaction.commit()?;
+                Ok(result)
+            }
+            Err(e) => {
+                // Error of `f` is more relevant than the rollback one
+                let _ = transaction.rollback();
+                Err(e)
+            }
+        }
+    }
+
+    fn migrate(&mut self, graph: &str, migrations: &Migrations) -> Result<Vec<i64>, Error> {
+        migrate_graph(self, graph, migrations, None)
+    }
+
+    fn migrate_to(
+        &mut self,
+        graph: &str,
+        migrations: &Migrations,
+        version: i64,
+    ) -> Result<Vec<i64>, Error> {
+        migrate_graph(self, graph, migrations, Some(version))
+    }
+
+    fn applied_migrations(&mut self, graph: &str) -> Result<Vec<AppliedMigration>, Error> {
+        let exists: bool = self.query_one(MIGRATIONS_TABLE_EXISTS, &[])?.try_get(0)?;
+        if !exists {
+            return Ok(vec![]);
+        }
+        let rows = self.query(APPLIED_MIGRATIONS, &[&graph])?;
+
+        rows.iter().map(AppliedMigration::from_row).collect()
+    }
+
     fn prepare_cypher(
         &mut self,
         graph: &str,
         cypher: &str,
         use_arg: bool,
-    ) -> Result<Statement, postgres::Error> {
-        let cypher_arg = if use_arg { CQ_ARG } else { CQ_NO_ARG };
-        let query = format!(cypher_query!(), graph, cypher, cypher_arg);
+    ) -> Result<Statement, Error> {
+        let query = cypher_query(graph, cypher, use_arg, &[DEFAULT_COLUMN])?;
+
+        Ok(self.prepare(&query)?)
+    }
+}
+
+/// Lazily decoded rows of the cypher query. See [`AgeClient::query_cypher_iter`]
+pub struct CypherIter<'a, R> {
+    transaction: Option<Transaction<'a>>,
+    portal: Portal,
+    batch_size: i32,
+    rows: std::vec::IntoIter<Row>,
+    _marker: PhantomData<fn() -> R>,
+}
+
+impl<R> CypherIter<'_, R> {
+    fn fetch(&mut self) -> Result<(), Error> {
+        let transaction = match self.transaction.as_mut() {
+            Some(transaction) => transaction,
+            None => return Ok(()),
+        };
+
+        let rows = transaction.query_portal(&self.portal, self.batch_size)?;
+
+        // Portal is exhausted when it returns less rows than requested
+        if rows.len() < self.batch_size as usize {
+            if let Some(transaction) = self.transaction.take() {
+                transaction.commit()?;
+            }
+        }
+
+        self.rows = rows.into_iter();
+        Ok(())
+    }
+}
+
+impl<R: FromAgRow> Iterator for CypherIter<'_, R> {
+    type Item = Result<R, Error>;
 
-        self.prepare(&query)
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rows.len() == 0 {
+            if let Err(e) = self.fetch() {
+                self.transaction = None;
+                return Some(Err(e));
+            }
+        }
+
+        self.rows.next().map(|row| R::from_ag_row(&row))
     }
 }
+
+/// Closure of the [`MigrationStep::Rust`](crate::MigrationStep::Rust) receiving the transaction and the graph name
+pub type MigrationFn = Box<dyn Fn(&mut Transaction<'_>, &str) -> Result<(), Error> + Send + Sync>;
+pub type Migration = crate::Migration<MigrationFn>;
+pub type MigrationStep = crate::MigrationStep<MigrationFn>;
+pub type Migrations = crate::Migrations<MigrationFn>;
+
+impl MigrationStep {
+    pub fn rust<F>(f: F) -> Self
+    where
+        F: Fn(&mut Transaction<'_>, &str) -> Result<(), Error> + Send + Sync + 'static,
+    {
+        crate::MigrationStep::Rust(Box::new(f))
+    }
+}
+
+fn migrate_graph<C: GenericClient>(
+    client: &mut C,
+    graph: &str,
+    migrations: &Migrations,
+    target: Option<i64>,
+) -> Result<Vec<i64>, Error> {
+    let graph = GraphName::new(graph)?;
+
+    client.with_graph_transaction(|tx| {
+        // Concurrent runs wait here until the first one commits
+        tx.execute(MIGRATIONS_LOCK, &[&lock_key(&graph)])?;
+        tx.batch_execute(CREATE_MIGRATIONS_TABLE)?;
+        if !tx.graph_exists(&graph)? {
+            tx.create_graph(&graph)?;
+        }
+
+        let applied = tx
+            .query(APPLIED_MIGRATIONS, &[&graph.as_str()])?
+            .iter()
+            .map(|x| x.try_get(0))
+            .collect::<Result<Vec<i64>, _>>()?;
+
+        let mut versions = vec![];
+        for (direction, migration) in migrations.plan(&applied, target)? {
+            match direction {
+                MigrationDirection::Up => {
+                    run_step(tx, &graph, migration.up_step())?;
+                    tx.execute(
+                        INSERT_MIGRATION,
+                        &[&graph.as_str(), &migration.version(), &migration.name()],
+                    )?;
+                }
+                MigrationDirection::Down => {
+                    if let Some(step) = migration.down_step() {
+                        run_step(tx, &graph, step)?;
+                    }
+                    tx.execute(DELETE_MIGRATION, &[&graph.as_str(), &migration.version()])?;
+                }
+            }
+            versions.push(migration.version());
+        }
+
+        Ok(versions)
+    })
+}
+
+fn run_step(tx: &mut Transaction<'_>, graph: &str, step: &MigrationStep) -> Result<(), Error> {
+    match step {
+        crate::MigrationStep::Cypher(cypher) => {
+            for statement in cypher_statements(cypher) {
+                tx.execute_cypher::<()>(graph, statement, None)?;
+            }
+        }
+        crate::MigrationStep::Sql(sql) => tx.batch_execute(sql)?,
+        crate::MigrationStep::Rust(f) => f(tx, graph)?,
+    }
+
+    Ok(())
+}
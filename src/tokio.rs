@@ -1,25 +1,49 @@
-use crate::AgType;
+use crate::{AgType, Vertex};
 use async_trait::async_trait;
-use serde::Serialize;
+use futures_core::{future::BoxFuture, Stream};
+use serde::{de::DeserializeOwned, Serialize};
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
 use tokio_postgres::{
-    connect,
-    tls::{MakeTlsConnect, TlsConnect},
-    Socket,
+    tls::{MakeTlsConnect, TlsConnect, TlsStream},
+    Connection, RowStream, Socket,
 };
 
 use super::constants::*;
+use crate::bulk::{vertices_cypher, RowBatches};
+use crate::catalog::{CountMethod, GraphInfo, GraphStats, LabelInfo, LabelStats};
+use crate::columns::{cypher_query, inferred_query, DEFAULT_COLUMN};
+use crate::config::{AgeConfig, ConnectionErrorCallback};
+use crate::constraint::{drop_constraint_query, Constraint, ConstraintInfo};
+use crate::identifier::{label_literals, label_table, property_literal, quote_name, GraphName};
+use crate::index::{drop_index_query, Index, IndexInfo};
+use crate::migrate::{cypher_statements, lock_key, AppliedMigration, MigrationDirection};
+use crate::params::CypherParams;
+use crate::row::FromAgRow;
 
+pub use crate::error::Error;
 pub use tokio::task::JoinHandle;
-pub use tokio_postgres::{Client, Error, Statement};
+
+/// Handle of the task driving the connection. Resolves with the error that closed the connection
+pub type ConnectionHandle = JoinHandle<Result<(), Error>>;
+pub use tokio_postgres::{Client, GenericClient, Statement, Transaction};
 
 #[async_trait]
 /// Handles connecting, configuring and querying graph dbs within postgres instance
+///
+/// Implemented for every [`GenericClient`], so the same methods can be used
+/// on the [`Client`], [`Transaction`] and nested savepoints
 pub trait AgeClient {
-    async fn connect_age<T>(
-        params: &str,
-        tls_mode: T,
-    ) -> Result<(Client, JoinHandle<()>), tokio_postgres::Error>
+    /// Connect and set up the AGE session. Connection string can be used in place of
+    /// [`AgeConfig`] to load `age` and set the default `search_path`.
+    ///
+    /// Connection is driven by the spawned task. Its handle resolves with the error
+    /// that closed the connection, which is also passed to [`AgeConfig::on_connection_error`]
+    /// (and emitted as a `tracing` event with the `tracing` feature)
+    async fn connect_age<P, T>(config: P, tls_mode: T) -> Result<(Client, ConnectionHandle), Error>
     where
+        P: Into<AgeConfig> + Send,
         T: MakeTlsConnect<Socket> + 'static + Send,
         T::TlsConnect: Send,
         T::Stream: Send,
@@ -27,25 +51,33 @@ pub trait AgeClient {
 
     /// Create a new constraint for the certain label within graph
     ///
-    /// **IMPORTANT**: At least one object has to be created with a certain label
+    /// Graph and label have to be valid [`GraphName`] and [`LabelName`](crate::LabelName), `name` is quoted.
+    /// `constraint_text` is inserted as is, so it must not contain untrusted input.
+    /// Prefer [`AgeClient::create_constraint`] for the property checks
+    ///
+    /// **IMPORTANT**: Label has to exist. Create it with [`AgeClient::create_vlabel`]
+    /// ([`AgeClient::create_elabel`]) or by creating at least one object with a certain label
     async fn constraint(
         &self,
         graph: &str,
         label: &str,
         name: &str,
         constraint_text: &str,
-    ) -> Result<u64, postgres::Error>;
+    ) -> Result<u64, Error>;
 
     /// Create unique index for the certain field for the label within graph
     ///
-    /// **IMPORTANT**: At least one object has to be created with a certain label
+    /// Graph and label have to be valid [`GraphName`] and [`LabelName`](crate::LabelName), `name` and `field` are quoted
+    ///
+    /// **IMPORTANT**: Label has to exist. Create it with [`AgeClient::create_vlabel`]
+    /// ([`AgeClient::create_elabel`]) or by creating at least one object with a certain label
     async fn unique_index(
         &self,
         graph: &str,
         label: &str,
         name: &str,
         field: &str,
-    ) -> Result<u64, postgres::Error>;
+    ) -> Result<u64, Error>;
 
     async fn required_constraint(
         &self,
@@ -53,11 +85,67 @@ pub trait AgeClient {
         label: &str,
         name: &str,
         field: &str,
-    ) -> Result<u64, postgres::Error>;
+    ) -> Result<u64, Error>;
+
+    /// Create constraint on the label table within graph, see [`Constraint`]
+    ///
+    /// **IMPORTANT**: Label has to exist
+    async fn create_constraint(
+        &self,
+        graph: &str,
+        label: &str,
+        constraint: &Constraint,
+    ) -> Result<u64, Error>;
+
+    /// `CHECK` constraints of the label table
+    async fn list_constraints(
+        &self,
+        graph: &str,
+        label: &str,
+    ) -> Result<Vec<ConstraintInfo>, Error>;
+
+    async fn drop_constraint(&self, graph: &str, label: &str, name: &str) -> Result<u64, Error>;
+
+    /// Create index on the label table within graph, see [`Index`]
+    ///
+    /// **IMPORTANT**: Label has to exist
+    async fn create_index(&self, graph: &str, label: &str, index: &Index) -> Result<u64, Error>;
+
+    /// Indexes of the label table, including the unique ones
+    async fn list_indexes(&self, graph: &str, label: &str) -> Result<Vec<IndexInfo>, Error>;
+
+    async fn drop_index(&self, graph: &str, name: &str) -> Result<u64, Error>;
+
+    async fn create_graph(&self, name: &str) -> Result<u64, Error>;
+    async fn drop_graph(&self, name: &str) -> Result<u64, Error>;
+    async fn graph_exists(&self, name: &str) -> Result<bool, Error>;
+
+    /// Create vertex label within graph, so indexes and constraints can be set up
+    /// before any vertex exists
+    async fn create_vlabel(&self, graph: &str, label: &str) -> Result<u64, Error>;
+
+    /// Create edge label within graph, so indexes and constraints can be set up
+    /// before any edge exists
+    async fn create_elabel(&self, graph: &str, label: &str) -> Result<u64, Error>;
 
-    async fn create_graph(&self, name: &str) -> Result<u64, postgres::Error>;
-    async fn drop_graph(&self, name: &str) -> Result<u64, postgres::Error>;
-    async fn graph_exists(&self, name: &str) -> Result<bool, postgres::Error>;
+    /// Drop label together with all its vertices or edges
+    async fn drop_label(&self, graph: &str, label: &str) -> Result<u64, Error>;
+
+    async fn label_exists(&self, graph: &str, label: &str) -> Result<bool, Error>;
+
+    /// Labels of the graph (without the default `_ag_label_vertex` and `_ag_label_edge`)
+    /// in the order of creation
+    async fn list_labels(&self, graph: &str) -> Result<Vec<LabelInfo>, Error>;
+
+    /// Names of all graphs in the database
+    async fn list_graphs(&self) -> Result<Vec<String>, Error>;
+
+    /// Graph with its labels. Returns [`Error::MissingGraph`] if it does not exist
+    async fn graph_info(&self, name: &str) -> Result<GraphInfo, Error>;
+
+    /// Number of vertices and edges per label. Exact counts scan every label table,
+    /// use [`CountMethod::Estimate`] for large graphs
+    async fn graph_stats(&self, name: &str, method: CountMethod) -> Result<GraphStats, Error>;
 
     /// Execute cypher query, without any rows to be retured
     async fn execute_cypher<T>(
@@ -65,7 +153,7 @@ pub trait AgeClient {
         graph: &str,
         cypher: &str,
         agtype: Option<AgType<T>>,
-    ) -> Result<u64, postgres::Error>
+    ) -> Result<u64, Error>
     where
         T: Serialize,
         T: std::fmt::Debug,
@@ -74,7 +162,8 @@ pub trait AgeClient {
 
     /// Query cypher for a single agtype (in a format of json)
     ///
-    /// **IMPORTANT**: You need to return result of the query as a map
+    /// **IMPORTANT**: You need to return result of the query as a map.
+    /// Use [`AgeClient::query_cypher_columns`] to return multiple columns
     ///
     /// Example:
     /// ```cypher
@@ -85,13 +174,221 @@ pub trait AgeClient {
         graph: &str,
         cypher: &str,
         agtype: Option<AgType<T>>,
-    ) -> Result<Vec<postgres::Row>, postgres::Error>
+    ) -> Result<Vec<postgres::Row>, Error>
+    where
+        T: Serialize,
+        T: std::fmt::Debug,
+        T: std::marker::Sync,
+        T: std::marker::Send;
+
+    /// Query cypher returning multiple agtype columns
+    ///
+    /// When `columns` are not provided, they are inferred from the `RETURN` clause
+    /// (see [`return_columns`](crate::return_columns)). Columns can be accessed by their names
+    /// or positions
+    ///
+    /// Example:
+    /// ```cypher
+    /// MATCH (n: Person) RETURN n.name, n.surname, count(*) AS total
+    /// ```
+    async fn query_cypher_columns<T>(
+        &self,
+        graph: &str,
+        cypher: &str,
+        columns: Option<&[&str]>,
+        agtype: Option<AgType<T>>,
+    ) -> Result<Vec<postgres::Row>, Error>
+    where
+        T: Serialize,
+        T: std::fmt::Debug,
+        T: std::marker::Sync,
+        T: std::marker::Send;
+
+    /// Query cypher and decode every row into `R`
+    ///
+    /// Columns are inferred from the `RETURN` clause. See [`FromAgRow`] for the supported types
+    ///
+    /// ```ignore
+    /// let people: Vec<(Vertex<Person>, AgType<i64>)> = client.query_cypher_as::<_, ()>(
+    ///     "graph",
+    ///     "MATCH (n: Person)-[:Knows]->(f) RETURN n, count(f)",
+    ///     None,
+    /// )?;
+    /// ```
+    async fn query_cypher_as<R, T>(
+        &self,
+        graph: &str,
+        cypher: &str,
+        agtype: Option<AgType<T>>,
+    ) -> Result<Vec<R>, Error>
+    where
+        R: FromAgRow + Send,
+        T: Serialize,
+        T: std::fmt::Debug,
+        T: std::marker::Sync,
+        T: std::marker::Send;
+
+    /// Query cypher for exactly one row decoded into `R`. Returns an error if the query
+    /// does not return exactly one row
+    async fn query_one_cypher_as<R, T>(
+        &self,
+        graph: &str,
+        cypher: &str,
+        agtype: Option<AgType<T>>,
+    ) -> Result<R, Error>
+    where
+        R: FromAgRow + Send,
+        T: Serialize,
+        T: std::fmt::Debug,
+        T: std::marker::Sync,
+        T: std::marker::Send;
+
+    /// Query cypher for at most one row decoded into `R`. Returns an error if the query
+    /// returns more than one row
+    async fn query_opt_cypher_as<R, T>(
+        &self,
+        graph: &str,
+        cypher: &str,
+        agtype: Option<AgType<T>>,
+    ) -> Result<Option<R>, Error>
+    where
+        R: FromAgRow + Send,
+        T: Serialize,
+        T: std::fmt::Debug,
+        T: std::marker::Sync,
+        T: std::marker::Send;
+
+    /// Query cypher and decode rows into `R` lazily, as they arrive from the database
+    ///
+    /// Columns are inferred from the `RETURN` clause, same as for [`AgeClient::query_cypher_as`]
+    ///
+    /// ```ignore
+    /// use futures_util::TryStreamExt;
+    ///
+    /// let mut people = client
+    ///     .query_cypher_stream::<Vertex<Person>, ()>("graph", "MATCH (n: Person) RETURN n", None)
+    ///     .await?;
+    ///
+    /// while let Some(person) = people.try_next().await? {
+    ///     println!("{}", person.properties().name);
+    /// }
+    /// ```
+    async fn query_cypher_stream<R, T>(
+        &self,
+        graph: &str,
+        cypher: &str,
+        agtype: Option<AgType<T>>,
+    ) -> Result<CypherStream<R>, Error>
     where
+        R: FromAgRow,
         T: Serialize,
         T: std::fmt::Debug,
         T: std::marker::Sync,
         T: std::marker::Send;
 
+    /// Execute cypher query with named parameters.
+    ///
+    /// Parameters are validated against the `$placeholders` of the query before it is sent
+    /// (see [`CypherParams::validate`])
+    ///
+    /// ```ignore
+    /// client.execute_cypher_params(
+    ///     "graph",
+    ///     "CREATE (n: Person {name: $name, age: $age})",
+    ///     &params! { name: "John", age: 42 },
+    /// ).await?;
+    /// ```
+    async fn execute_cypher_params(
+        &self,
+        graph: &str,
+        cypher: &str,
+        params: &CypherParams,
+    ) -> Result<u64, Error>;
+
+    /// Query cypher with named parameters and decode every row into `R`.
+    ///
+    /// Parameters are validated same as for [`AgeClient::execute_cypher_params`],
+    /// columns are inferred same as for [`AgeClient::query_cypher_as`]
+    async fn query_cypher_params<R>(
+        &self,
+        graph: &str,
+        cypher: &str,
+        params: &CypherParams,
+    ) -> Result<Vec<R>, Error>
+    where
+        R: FromAgRow + Send;
+
+    /// Create a vertex with the label out of every row (serialized as a map of properties).
+    ///
+    /// Rows are sent in batches of `batch_size` rows
+    /// ([`DEFAULT_INSERT_BATCH_SIZE`](crate::DEFAULT_INSERT_BATCH_SIZE) when `None`) as a single
+    /// list parameter of the `UNWIND $rows AS r CREATE (n:Label) SET n = r` query.
+    /// Every batch is a separate statement, so use [`AgeClient::with_graph_transaction`]
+    /// to create all vertices or none. Returns the number of created vertices
+    async fn create_vertices<T, I>(
+        &self,
+        graph: &str,
+        label: &str,
+        rows: I,
+        batch_size: Option<usize>,
+    ) -> Result<u64, Error>
+    where
+        I: IntoIterator<Item = T> + Send,
+        I::IntoIter: Send,
+        T: Serialize + Send;
+
+    /// Same as [`AgeClient::create_vertices`], but returns the created vertices
+    async fn create_vertices_returning<T, I>(
+        &self,
+        graph: &str,
+        label: &str,
+        rows: I,
+        batch_size: Option<usize>,
+    ) -> Result<Vec<Vertex<T>>, Error>
+    where
+        I: IntoIterator<Item = T> + Send,
+        I::IntoIter: Send,
+        T: Serialize + DeserializeOwned + Send;
+
+    /// Run `f` within a transaction, which is committed when the returned future succeeds
+    /// and rolled back otherwise.
+    ///
+    /// Called on the transaction it creates a savepoint instead, so multi-step graph writes
+    /// can be nested
+    ///
+    /// ```ignore
+    /// client
+    ///     .with_graph_transaction(|tx| {
+    ///         Box::pin(async move {
+    ///             tx.execute_cypher::<()>("graph", "CREATE (n: Person {name: 'John'})", None)
+    ///                 .await?;
+    ///             tx.unique_index("graph", "Person", "person_name_idx", "name").await?;
+    ///             Ok(())
+    ///         })
+    ///     })
+    ///     .await?;
+    /// ```
+    async fn with_graph_transaction<R, F>(&mut self, f: F) -> Result<R, Error>
+    where
+        R: Send,
+        F: for<'t> FnOnce(&'t mut Transaction<'_>) -> BoxFuture<'t, Result<R, Error>> + Send;
+
+    /// Apply pending migrations to the graph (created when missing).
+    /// Returns versions of the applied migrations. See [`Migrations`]
+    async fn migrate(&mut self, graph: &str, migrations: &Migrations) -> Result<Vec<i64>, Error>;
+
+    /// Apply or revert migrations, so `version` becomes the latest applied one.
+    /// Returns versions of the applied (reverted) migrations in the order they were run
+    async fn migrate_to(
+        &mut self,
+        graph: &str,
+        migrations: &Migrations,
+        version: i64,
+    ) -> Result<Vec<i64>, Error>;
+
+    /// Migrations applied to the graph in the order of versions
+    async fn applied_migrations(&self, graph: &str) -> Result<Vec<AppliedMigration>, Error>;
+
     /// Prepare cypher query for future use
     /// ```
     #[doc = include_str!("../examples/prepared_statements_async.rs")]
@@ -101,45 +398,41 @@ pub trait AgeClient {
         graph: &str,
         cypher: &str,
         use_arg: bool,
-    ) -> Result<Statement, postgres::Error>;
+    ) -> Result<Statement, Error>;
 }
 
 #[async_trait]
-impl AgeClient for Client {
-    async fn create_graph(&self, name: &str) -> Result<u64, postgres::Error> {
-        self.execute(CREATE_GRAPH, &[&name]).await
+impl<C> AgeClient for C
+where
+    C: GenericClient + Send + Sync,
+{
+    async fn create_graph(&self, name: &str) -> Result<u64, Error> {
+        let name = GraphName::new(name)?;
+        Ok(self.execute(CREATE_GRAPH, &[&name.as_str()]).await?)
     }
 
-    async fn drop_graph(&self, name: &str) -> Result<u64, postgres::Error> {
-        self.execute(DROP_GRAPH, &[&name]).await
+    async fn drop_graph(&self, name: &str) -> Result<u64, Error> {
+        Ok(self.execute(DROP_GRAPH, &[&name]).await?)
     }
 
-    async fn connect_age<T>(
-        params: &str,
-        tls_mode: T,
-    ) -> Result<(Client, JoinHandle<()>), tokio_postgres::Error>
+    async fn connect_age<P, T>(config: P, tls_mode: T) -> Result<(Client, ConnectionHandle), Error>
     where
+        P: Into<AgeConfig> + Send,
         T: MakeTlsConnect<Socket> + 'static + Send,
         T::TlsConnect: Send,
         T::Stream: Send,
         <T::TlsConnect as TlsConnect<Socket>>::Future: Send,
     {
-        let new_connection = connect(params, tls_mode).await?;
+        let config = config.into();
+        let (client, connection) = config
+            .pg_config()?
+            .connect(tls_mode)
+            .await
+            .map_err(Error::Connection)?;
 
-        let (client, connection) = new_connection;
+        let handle = spawn_connection(connection, config.connection_error_callback());
 
-        let handle = tokio::spawn(async move {
-            if let Err(e) = connection.await {
-                eprintln!("connection error: {}", e);
-            }
-        });
-
-        for query in [
-            client.simple_query(LOAD_AGE).await,
-            client.simple_query(SET_AGE).await,
-        ] {
-            query?;
-        }
+        client.batch_execute(&config.setup_query()).await?;
         Ok((client, handle))
     }
 
@@ -148,7 +441,7 @@ impl AgeClient for Client {
         graph: &str,
         cypher: &str,
         agtype: Option<AgType<T>>,
-    ) -> Result<Vec<postgres::Row>, postgres::Error>
+    ) -> Result<Vec<postgres::Row>, Error>
     where
         T: Serialize,
         T: std::fmt::Debug,
@@ -157,28 +450,231 @@ impl AgeClient for Client {
     {
         match agtype {
             Some(x) => {
-                let query = format!(cypher_query!(), graph, cypher, CQ_ARG);
+                let query = cypher_query(graph, cypher, true, &[DEFAULT_COLUMN])?;
 
-                self.query(&query, &[&x]).await
+                Ok(self.query(&query, &[&x]).await?)
             }
             None => {
-                let query = format!(cypher_query!(), graph, cypher, CQ_NO_ARG);
+                let query = cypher_query(graph, cypher, false, &[DEFAULT_COLUMN])?;
 
-                self.query(&query, &[]).await
+                Ok(self.query(&query, &[]).await?)
             }
         }
     }
 
+    async fn query_cypher_columns<T>(
+        &self,
+        graph: &str,
+        cypher: &str,
+        columns: Option<&[&str]>,
+        agtype: Option<AgType<T>>,
+    ) -> Result<Vec<postgres::Row>, Error>
+    where
+        T: Serialize,
+        T: std::fmt::Debug,
+        T: std::marker::Sync,
+        T: std::marker::Send,
+    {
+        let query = match columns {
+            Some(columns) => cypher_query(graph, cypher, agtype.is_some(), columns)?,
+            None => inferred_query(graph, cypher, agtype.is_some())?,
+        };
+
+        match &agtype {
+            Some(x) => Ok(self.query(&query, &[x]).await?),
+            None => Ok(self.query(&query, &[]).await?),
+        }
+    }
+
+    async fn query_cypher_as<R, T>(
+        &self,
+        graph: &str,
+        cypher: &str,
+        agtype: Option<AgType<T>>,
+    ) -> Result<Vec<R>, Error>
+    where
+        R: FromAgRow + Send,
+        T: Serialize,
+        T: std::fmt::Debug,
+        T: std::marker::Sync,
+        T: std::marker::Send,
+    {
+        let query = inferred_query(graph, cypher, agtype.is_some())?;
+        let rows = match &agtype {
+            Some(x) => self.query(&query, &[x]).await?,
+            None => self.query(&query, &[]).await?,
+        };
+
+        rows.iter().map(R::from_ag_row).collect()
+    }
+
+    async fn query_one_cypher_as<R, T>(
+        &self,
+        graph: &str,
+        cypher: &str,
+        agtype: Option<AgType<T>>,
+    ) -> Result<R, Error>
+    where
+        R: FromAgRow + Send,
+        T: Serialize,
+        T: std::fmt::Debug,
+        T: std::marker::Sync,
+        T: std::marker::Send,
+    {
+        let query = inferred_query(graph, cypher, agtype.is_some())?;
+        let row = match &agtype {
+            Some(x) => self.query_one(&query, &[x]).await?,
+            None => self.query_one(&query, &[]).await?,
+        };
+
+        R::from_ag_row(&row)
+    }
+
+    async fn query_opt_cypher_as<R, T>(
+        &self,
+        graph: &str,
+        cypher: &str,
+        agtype: Option<AgType<T>>,
+    ) -> Result<Option<R>, Error>
+    where
+        R: FromAgRow + Send,
+        T: Serialize,
+        T: std::fmt::Debug,
+        T: std::marker::Sync,
+        T: std::marker::Send,
+    {
+        let query = inferred_query(graph, cypher, agtype.is_some())?;
+        let row = match &agtype {
+            Some(x) => self.query_opt(&query, &[x]).await?,
+            None => self.query_opt(&query, &[]).await?,
+        };
+
+        row.as_ref().map(R::from_ag_row).transpose()
+    }
+
+    async fn query_cypher_stream<R, T>(
+        &self,
+        graph: &str,
+        cypher: &str,
+        agtype: Option<AgType<T>>,
+    ) -> Result<CypherStream<R>, Error>
+    where
+        R: FromAgRow,
+        T: Serialize,
+        T: std::fmt::Debug,
+        T: std::marker::Sync,
+        T: std::marker::Send,
+    {
+        let query = inferred_query(graph, cypher, agtype.is_some())?;
+        let rows = self.query_raw(&query, agtype.iter()).await?;
+
+        Ok(CypherStream {
+            rows: Box::pin(rows),
+            _marker: PhantomData,
+        })
+    }
+
+    async fn execute_cypher_params(
+        &self,
+        graph: &str,
+        cypher: &str,
+        params: &CypherParams,
+    ) -> Result<u64, Error> {
+        params.validate(cypher)?;
+        if params.is_empty() {
+            let query = cypher_query(graph, cypher, false, &[DEFAULT_COLUMN])?;
+            Ok(self.execute(&query, &[]).await?)
+        } else {
+            let query = cypher_query(graph, cypher, true, &[DEFAULT_COLUMN])?;
+            Ok(self.execute(&query, &[params]).await?)
+        }
+    }
+
+    async fn query_cypher_params<R>(
+        &self,
+        graph: &str,
+        cypher: &str,
+        params: &CypherParams,
+    ) -> Result<Vec<R>, Error>
+    where
+        R: FromAgRow + Send,
+    {
+        params.validate(cypher)?;
+        let query = inferred_query(graph, cypher, !params.is_empty())?;
+        let rows = if params.is_empty() {
+            self.query(&query, &[]).await?
+        } else {
+            self.query(&query, &[params]).await?
+        };
+
+        rows.iter().map(R::from_ag_row).collect()
+    }
+
+    async fn create_vertices<T, I>(
+        &self,
+        graph: &str,
+        label: &str,
+        rows: I,
+        batch_size: Option<usize>,
+    ) -> Result<u64, Error>
+    where
+        I: IntoIterator<Item = T> + Send,
+        I::IntoIter: Send,
+        T: Serialize + Send,
+    {
+        let cypher = vertices_cypher(label, false)?;
+        let mut created = 0;
+
+        for batch in RowBatches::new(rows, batch_size) {
+            let (count, params) = batch?;
+            self.execute_cypher_params(graph, &cypher, &params).await?;
+            created += count;
+        }
+
+        Ok(created)
+    }
+
+    async fn create_vertices_returning<T, I>(
+        &self,
+        graph: &str,
+        label: &str,
+        rows: I,
+        batch_size: Option<usize>,
+    ) -> Result<Vec<Vertex<T>>, Error>
+    where
+        I: IntoIterator<Item = T> + Send,
+        I::IntoIter: Send,
+        T: Serialize + DeserializeOwned + Send,
+    {
+        let cypher = vertices_cypher(label, true)?;
+        let mut vertices = vec![];
+
+        for batch in RowBatches::new(rows, batch_size) {
+            let (_, params) = batch?;
+            vertices.extend(
+                self.query_cypher_params::<Vertex<T>>(graph, &cypher, &params)
+                    .await?,
+            );
+        }
+
+        Ok(vertices)
+    }
+
     async fn constraint(
         &self,
         graph: &str,
         label: &str,
         name: &str,
         constraint_text: &str,
-    ) -> Result<u64, postgres::Error> {
-        let query = format!(constraint!(), graph, label, name, constraint_text);
+    ) -> Result<u64, Error> {
+        let query = format!(
+            constraint!(),
+            label_table(graph, label)?,
+            quote_name(name)?,
+            constraint_text
+        );
 
-        self.execute(&query, &[]).await
+        Ok(self.execute(&query, &[]).await?)
     }
 
     async fn unique_index(
@@ -187,10 +683,15 @@ impl AgeClient for Client {
         label: &str,
         name: &str,
         field: &str,
-    ) -> Result<u64, postgres::Error> {
-        let query = format!(unique_index!(), name, graph, label, field);
+    ) -> Result<u64, Error> {
+        let query = format!(
+            unique_index!(),
+            quote_name(name)?,
+            label_table(graph, label)?,
+            property_literal(field)
+        );
 
-        self.execute(&query, &[]).await
+        Ok(self.execute(&query, &[]).await?)
     }
 
     async fn required_constraint(
@@ -199,9 +700,14 @@ impl AgeClient for Client {
         label: &str,
         name: &str,
         field: &str,
-    ) -> Result<u64, postgres::Error> {
-        self.constraint(graph, label, name, &format!(required_constraint!(), field))
-            .await
+    ) -> Result<u64, Error> {
+        self.constraint(
+            graph,
+            label,
+            name,
+            &format!(required_constraint!(), property_literal(field)),
+        )
+        .await
     }
 
     async fn execute_cypher<T>(
@@ -209,7 +715,7 @@ impl AgeClient for Client {
         graph: &str,
         cypher: &str,
         agtype: Option<AgType<T>>,
-    ) -> Result<u64, postgres::Error>
+    ) -> Result<u64, Error>
     where
         T: Serialize,
         T: std::fmt::Debug,
@@ -218,26 +724,191 @@ impl AgeClient for Client {
     {
         match agtype {
             Some(x) => {
-                let query = format!(cypher_query!(), graph, cypher, CQ_ARG);
+                let query = cypher_query(graph, cypher, true, &[DEFAULT_COLUMN])?;
 
-                self.execute(&query, &[&x]).await
+                Ok(self.execute(&query, &[&x]).await?)
             }
             None => {
-                let query = format!(cypher_query!(), graph, cypher, CQ_NO_ARG);
+                let query = cypher_query(graph, cypher, false, &[DEFAULT_COLUMN])?;
 
-                self.execute(&query, &[]).await
+                Ok(self.execute(&query, &[]).await?)
             }
         }
     }
 
-    async fn graph_exists(&self, name: &str) -> Result<bool, postgres::Error> {
+    async fn create_constraint(
+        &self,
+        graph: &str,
+        label: &str,
+        constraint: &Constraint,
+    ) -> Result<u64, Error> {
+        let query = constraint.to_sql(graph, label)?;
+
+        Ok(self.execute(&query, &[]).await?)
+    }
+
+    async fn list_constraints(
+        &self,
+        graph: &str,
+        label: &str,
+    ) -> Result<Vec<ConstraintInfo>, Error> {
+        let rows = self.query(LIST_CONSTRAINTS, &[&graph, &label]).await?;
+
+        rows.iter().map(ConstraintInfo::from_row).collect()
+    }
+
+    async fn drop_constraint(&self, graph: &str, label: &str, name: &str) -> Result<u64, Error> {
+        let query = drop_constraint_query(graph, label, name)?;
+
+        Ok(self.execute(&query, &[]).await?)
+    }
+
+    async fn create_index(&self, graph: &str, label: &str, index: &Index) -> Result<u64, Error> {
+        let query = index.to_sql(graph, label)?;
+
+        Ok(self.execute(&query, &[]).await?)
+    }
+
+    async fn list_indexes(&self, graph: &str, label: &str) -> Result<Vec<IndexInfo>, Error> {
+        let rows = self.query(LIST_INDEXES, &[&graph, &label]).await?;
+
+        rows.iter().map(IndexInfo::from_row).collect()
+    }
+
+    async fn drop_index(&self, graph: &str, name: &str) -> Result<u64, Error> {
+        let query = drop_index_query(graph, name)?;
+
+        Ok(self.execute(&query, &[]).await?)
+    }
+
+    async fn graph_exists(&self, name: &str) -> Result<bool, Error> {
         match self.query(GRAPH_EXISTS, &[&name.to_string()]).await {
             Ok(result) => {
                 let x: i64 = result[0].get(0);
                 return Ok(x == 1);
             }
-            Err(e) => return Err(e),
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    async fn create_vlabel(&self, graph: &str, label: &str) -> Result<u64, Error> {
+        let (graph, label) = label_literals(graph, label)?;
+        let query = format!(create_vlabel!(), graph, label);
+
+        Ok(self.execute(&query, &[]).await?)
+    }
+
+    async fn create_elabel(&self, graph: &str, label: &str) -> Result<u64, Error> {
+        let (graph, label) = label_literals(graph, label)?;
+        let query = format!(create_elabel!(), graph, label);
+
+        Ok(self.execute(&query, &[]).await?)
+    }
+
+    async fn drop_label(&self, graph: &str, label: &str) -> Result<u64, Error> {
+        let (graph, label) = label_literals(graph, label)?;
+        let query = format!(drop_label!(), graph, label);
+
+        Ok(self.execute(&query, &[]).await?)
+    }
+
+    async fn label_exists(&self, graph: &str, label: &str) -> Result<bool, Error> {
+        let row = self.query_one(LABEL_EXISTS, &[&graph, &label]).await?;
+        let count: i64 = row.get(0);
+
+        Ok(count == 1)
+    }
+
+    async fn list_labels(&self, graph: &str) -> Result<Vec<LabelInfo>, Error> {
+        let rows = self.query(LIST_LABELS, &[&graph]).await?;
+
+        rows.iter().map(LabelInfo::from_row).collect()
+    }
+
+    async fn list_graphs(&self) -> Result<Vec<String>, Error> {
+        let rows = self.query(LIST_GRAPHS, &[]).await?;
+
+        Ok(rows
+            .iter()
+            .map(|x| x.try_get(0))
+            .collect::<Result<_, _>>()?)
+    }
+
+    async fn graph_info(&self, name: &str) -> Result<GraphInfo, Error> {
+        let row = self
+            .query_opt(GRAPH_INFO, &[&name])
+            .await?
+            .ok_or_else(|| Error::MissingGraph(name.to_string()))?;
+        let labels = self.list_labels(name).await?;
+
+        GraphInfo::from_row(&row, labels)
+    }
+
+    async fn graph_stats(&self, name: &str, method: CountMethod) -> Result<GraphStats, Error> {
+        let rows = self.query(LABEL_STATS, &[&name]).await?;
+        // Every graph has the default labels
+        if rows.is_empty() {
+            return Err(Error::MissingGraph(name.to_string()));
+        }
+
+        let mut labels = rows
+            .iter()
+            .map(LabelStats::from_row)
+            .collect::<Result<Vec<_>, _>>()?;
+        if method == CountMethod::Exact {
+            for label in labels.iter_mut() {
+                let query = format!(count_label!(), label_table(name, &label.name)?);
+                label.count = self.query_one(&query, &[]).await?.try_get(0)?;
+            }
+        }
+
+        Ok(GraphStats { labels })
+    }
+
+    async fn with_graph_transaction<R, F>(&mut self, f: F) -> Result<R, Error>
+    where
+        R: Send,
+        F: for<'t> FnOnce(&'t mut Transaction<'_>) -> BoxFuture<'t, Result<R, Error>> + Send,
+    {
+        let mut transaction = self.transaction().await?;
+
+        match f(&mut transaction).await {
+            Ok(result) => {
+                transaction.commit().await?;
+                Ok(result)
+            }
+            Err(e) => {
+                // Error of `f` is more relevant than the rollback one
+                let _ = transaction.rollback().await;
+                Err(e)
+            }
+        }
+    }
+
+    async fn migrate(&mut self, graph: &str, migrations: &Migrations) -> Result<Vec<i64>, Error> {
+        migrate_graph(self, graph, migrations, None).await
+    }
+
+    async fn migrate_to(
+        &mut self,
+        graph: &str,
+        migrations: &Migrations,
+        version: i64,
+    ) -> Result<Vec<i64>, Error> {
+        migrate_graph(self, graph, migrations, Some(version)).await
+    }
+
+    async fn applied_migrations(&self, graph: &str) -> Result<Vec<AppliedMigration>, Error> {
+        let exists: bool = self
+            .query_one(MIGRATIONS_TABLE_EXISTS, &[])
+            .await?
+            .try_get(0)?;
+        if !exists {
+            return Ok(vec![]);
         }
+        let rows = self.query(APPLIED_MIGRATIONS, &[&graph]).await?;
+
+        rows.iter().map(AppliedMigration::from_row).collect()
     }
 
     async fn prepare_cypher(
@@ -245,10 +916,169 @@ impl AgeClient for Client {
         graph: &str,
         cypher: &str,
         use_arg: bool,
-    ) -> Result<Statement, postgres::Error> {
-        let cypher_arg = if use_arg { CQ_ARG } else { CQ_NO_ARG };
-        let query = format!(cypher_query!(), graph, cypher, cypher_arg);
+    ) -> Result<Statement, Error> {
+        let query = cypher_query(graph, cypher, use_arg, &[DEFAULT_COLUMN])?;
+
+        Ok(self.prepare(&query).await?)
+    }
+}
+
+/// Drives the connection in the background task. Errors are reported to the callback
+/// (and `tracing`) and returned by the task
+pub(crate) fn spawn_connection<S>(
+    connection: Connection<Socket, S>,
+    on_error: Option<ConnectionErrorCallback>,
+) -> ConnectionHandle
+where
+    S: TlsStream + Unpin + Send + 'static,
+{
+    tokio::spawn(async move {
+        match connection.await {
+            Ok(()) => {
+                #[cfg(feature = "tracing")]
+                tracing::debug!("AGE connection closed");
+                Ok(())
+            }
+            Err(e) => {
+                let e = Error::Connection(e);
+                #[cfg(feature = "tracing")]
+                tracing::error!(error = %e, "AGE connection failed");
+                if let Some(on_error) = on_error {
+                    on_error(&e);
+                }
+                Err(e)
+            }
+        }
+    })
+}
+
+/// Lazily decoded rows of the cypher query. See [`AgeClient::query_cypher_stream`]
+pub struct CypherStream<R> {
+    rows: Pin<Box<RowStream>>,
+    _marker: PhantomData<fn() -> R>,
+}
+
+impl<R: FromAgRow> Stream for CypherStream<R> {
+    type Item = Result<R, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rows.as_mut().poll_next(cx).map(|row| {
+            row.map(|row| {
+                row.map_err(Error::from)
+                    .and_then(|row| R::from_ag_row(&row))
+            })
+        })
+    }
+}
+
+/// Closure of the [`MigrationStep::Rust`](crate::MigrationStep::Rust) receiving the transaction and the graph name
+pub type MigrationFn = Box<
+    dyn for<'t> Fn(&'t mut Transaction<'_>, &'t str) -> BoxFuture<'t, Result<(), Error>>
+        + Send
+        + Sync,
+>;
+pub type Migration = crate::Migration<MigrationFn>;
+pub type MigrationStep = crate::MigrationStep<MigrationFn>;
+pub type Migrations = crate::Migrations<MigrationFn>;
+
+impl MigrationStep {
+    pub fn rust<F>(f: F) -> Self
+    where
+        F: for<'t> Fn(&'t mut Transaction<'_>, &'t str) -> BoxFuture<'t, Result<(), Error>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        crate::MigrationStep::Rust(Box::new(f))
+    }
+}
+
+// `with_graph_transaction` cannot be used, as its future has to outlive
+// the borrowed migrations
+async fn migrate_graph<C>(
+    client: &mut C,
+    graph: &str,
+    migrations: &Migrations,
+    target: Option<i64>,
+) -> Result<Vec<i64>, Error>
+where
+    C: GenericClient + Send + Sync,
+{
+    let graph = GraphName::new(graph)?;
+    let mut transaction = client.transaction().await?;
 
-        self.prepare(&query).await
+    match run_migrations(&mut transaction, &graph, migrations, target).await {
+        Ok(versions) => {
+            transaction.commit().await?;
+            Ok(versions)
+        }
+        Err(e) => {
+            // Error of the migration is more relevant than the rollback one
+            let _ = transaction.rollback().await;
+            Err(e)
+        }
     }
 }
+
+async fn run_migrations(
+    tx: &mut Transaction<'_>,
+    graph: &str,
+    migrations: &Migrations,
+    target: Option<i64>,
+) -> Result<Vec<i64>, Error> {
+    // Concurrent runs wait here until the first one commits
+    tx.execute(MIGRATIONS_LOCK, &[&lock_key(graph)]).await?;
+    tx.batch_execute(CREATE_MIGRATIONS_TABLE).await?;
+    if !tx.graph_exists(graph).await? {
+        tx.create_graph(graph).await?;
+    }
+
+    let applied = tx
+        .query(APPLIED_MIGRATIONS, &[&graph])
+        .await?
+        .iter()
+        .map(|x| x.try_get(0))
+        .collect::<Result<Vec<i64>, _>>()?;
+
+    let mut versions = vec![];
+    for (direction, migration) in migrations.plan(&applied, target)? {
+        match direction {
+            MigrationDirection::Up => {
+                run_step(tx, graph, migration.up_step()).await?;
+                tx.execute(
+                    INSERT_MIGRATION,
+                    &[&graph, &migration.version(), &migration.name()],
+                )
+                .await?;
+            }
+            MigrationDirection::Down => {
+                if let Some(step) = migration.down_step() {
+                    run_step(tx, graph, step).await?;
+                }
+                tx.execute(DELETE_MIGRATION, &[&graph, &migration.version()])
+                    .await?;
+            }
+        }
+        versions.push(migration.version());
+    }
+
+    Ok(versions)
+}
+
+async fn run_step(
+    tx: &mut Transaction<'_>,
+    graph: &str,
+    step: &MigrationStep,
+) -> Result<(), Error> {
+    match step {
+        crate::MigrationStep::Cypher(cypher) => {
+            for statement in cypher_statements(cypher) {
+                tx.execute_cypher::<()>(graph, statement, None).await?;
+            }
+        }
+        crate::MigrationStep::Sql(sql) => tx.batch_execute(sql).await?,
+        crate::MigrationStep::Rust(f) => f(tx, graph).await?,
+    }
+
+    Ok(())
+}
@@ -0,0 +1,297 @@
+use crate::age_types::{Edge, Path, Vertex};
+use crate::agtype::{decode_column, DecodeError, Parser};
+use bytes::BufMut;
+use postgres_types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
+use std::collections::BTreeMap;
+use std::fmt::{self, Write as _};
+use std::io::Write;
+
+/// Properties of the dynamically decoded vertices and edges
+pub type AgMap = BTreeMap<String, AgValue>;
+
+/// Arbitrary precision number (`::numeric` annotation). Kept in its textual form
+///
+/// With the `bigdecimal` feature it can be converted from and into the [`bigdecimal::BigDecimal`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Numeric(pub(crate) String);
+
+impl Numeric {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Lossy conversion into the float
+    pub fn to_f64(&self) -> Option<f64> {
+        self.0.parse().ok()
+    }
+
+    #[cfg(feature = "bigdecimal")]
+    pub fn to_bigdecimal(
+        &self,
+    ) -> Result<bigdecimal::BigDecimal, bigdecimal::ParseBigDecimalError> {
+        self.0.parse()
+    }
+}
+
+/// Newtype name used to recognise [`Numeric`] inside of the serializers
+pub(crate) const NUMERIC_TOKEN: &str = "$apache_age::private::Numeric";
+
+/// Serialized as a string by the generic serializers (e.g. `serde_json`), so no precision is lost.
+/// Cypher literal serializer renders it with the `::numeric` annotation
+impl serde::Serialize for Numeric {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_newtype_struct(NUMERIC_TOKEN, &self.0)
+    }
+}
+
+impl fmt::Display for Numeric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(feature = "bigdecimal")]
+impl From<bigdecimal::BigDecimal> for Numeric {
+    fn from(value: bigdecimal::BigDecimal) -> Self {
+        Numeric(value.to_string())
+    }
+}
+
+#[cfg(feature = "bigdecimal")]
+impl TryFrom<&Numeric> for bigdecimal::BigDecimal {
+    type Error = bigdecimal::ParseBigDecimalError;
+
+    fn try_from(value: &Numeric) -> Result<Self, Self::Error> {
+        value.to_bigdecimal()
+    }
+}
+
+/// Represents any value that can be returned within agtype.
+///
+/// Useful when shape of the result is not known ahead of time
+#[derive(Debug, Clone, PartialEq)]
+pub enum AgValue {
+    Null,
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    Numeric(Numeric),
+    String(String),
+    List(Vec<AgValue>),
+    Map(AgMap),
+    Vertex(Vertex<AgMap>),
+    Edge(Edge<AgMap>),
+    Path(Path<AgMap, AgMap>),
+}
+
+impl AgValue {
+    pub fn is_null(&self) -> bool {
+        matches!(self, AgValue::Null)
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            AgValue::Bool(x) => Some(*x),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            AgValue::Integer(x) => Some(*x),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            AgValue::Integer(x) => Some(*x as f64),
+            AgValue::Float(x) => Some(*x),
+            AgValue::Numeric(x) => x.to_f64(),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            AgValue::String(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&Vec<AgValue>> {
+        match self {
+            AgValue::List(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    pub fn as_map(&self) -> Option<&AgMap> {
+        match self {
+            AgValue::Map(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    pub fn as_vertex(&self) -> Option<&Vertex<AgMap>> {
+        match self {
+            AgValue::Vertex(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    pub fn as_edge(&self) -> Option<&Edge<AgMap>> {
+        match self {
+            AgValue::Edge(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    pub fn as_path(&self) -> Option<&Path<AgMap, AgMap>> {
+        match self {
+            AgValue::Path(x) => Some(x),
+            _ => None,
+        }
+    }
+}
+
+impl std::str::FromStr for AgValue {
+    type Err = DecodeError;
+
+    /// Parses agtype text representation (without the binary header)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Parser::parse(s.as_bytes())
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
+
+fn write_map(f: &mut fmt::Formatter<'_>, map: &AgMap) -> fmt::Result {
+    f.write_char('{')?;
+    for (i, (key, value)) in map.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        write_string(f, key)?;
+        write!(f, ": {}", value)?;
+    }
+    f.write_char('}')
+}
+
+fn write_vertex(f: &mut fmt::Formatter<'_>, vertex: &Vertex<AgMap>) -> fmt::Result {
+    write!(f, "{{\"id\": {}, \"label\": ", vertex.id)?;
+    write_string(f, &vertex.label)?;
+    f.write_str(", \"properties\": ")?;
+    write_map(f, &vertex.properties)?;
+    f.write_str("}::vertex")
+}
+
+fn write_edge(f: &mut fmt::Formatter<'_>, edge: &Edge<AgMap>) -> fmt::Result {
+    write!(f, "{{\"id\": {}, \"label\": ", edge.id)?;
+    write_string(f, &edge.label)?;
+    write!(
+        f,
+        ", \"end_id\": {}, \"start_id\": {}, \"properties\": ",
+        edge.end_id, edge.start_id
+    )?;
+    write_map(f, &edge.properties)?;
+    f.write_str("}::edge")
+}
+
+/// Writes value using the agtype text representation
+impl fmt::Display for AgValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AgValue::Null => f.write_str("null"),
+            AgValue::Bool(x) => write!(f, "{}", x),
+            AgValue::Integer(x) => write!(f, "{}", x),
+            AgValue::Float(x) if x.is_nan() => f.write_str("NaN"),
+            AgValue::Float(x) if x.is_infinite() && *x > 0.0 => f.write_str("Infinity"),
+            AgValue::Float(x) if x.is_infinite() => f.write_str("-Infinity"),
+            AgValue::Float(x) => write!(f, "{:?}", x),
+            AgValue::Numeric(x) => write!(f, "{}::numeric", x.0),
+            AgValue::String(x) => write_string(f, x),
+            AgValue::List(x) => {
+                f.write_char('[')?;
+                for (i, value) in x.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                f.write_char(']')
+            }
+            AgValue::Map(x) => write_map(f, x),
+            AgValue::Vertex(x) => write_vertex(f, x),
+            AgValue::Edge(x) => write_edge(f, x),
+            AgValue::Path(x) => {
+                f.write_char('[')?;
+                for (i, vertex) in x.vertices.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                        write_edge(f, &x.edges[i - 1])?;
+                        f.write_str(", ")?;
+                    }
+                    write_vertex(f, vertex)?;
+                }
+                f.write_str("]::path")
+            }
+        }
+    }
+}
+
+/// Value is sent using its agtype text representation
+impl ToSql for AgValue {
+    fn accepts(ty: &Type) -> bool {
+        ty.schema() == "ag_catalog" && ty.name() == "agtype"
+    }
+
+    fn to_sql(
+        &self,
+        _ty: &Type,
+        out: &mut postgres_types::private::BytesMut,
+    ) -> Result<postgres_types::IsNull, Box<dyn std::error::Error + Sync + Send>>
+    where
+        Self: Sized,
+    {
+        out.put_u8(1);
+        write!(out.writer(), "{}", self)?;
+        Ok(IsNull::No)
+    }
+
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for AgValue {
+    fn from_sql(
+        ty: &Type,
+        raw: &'a [u8],
+    ) -> Result<AgValue, Box<dyn std::error::Error + Sync + Send>> {
+        decode_column(ty, raw, Parser::parse)
+    }
+
+    fn from_sql_null(_ty: &Type) -> Result<AgValue, Box<dyn std::error::Error + Sync + Send>> {
+        Ok(AgValue::Null)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        ty.schema() == "ag_catalog" && ty.name() == "agtype"
+    }
+}
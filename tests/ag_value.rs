@@ -0,0 +1,70 @@
+use apache_age::{AgValue, GraphId};
+use postgres_types::{FromSql, Kind, Type};
+
+fn agtype() -> Type {
+    Type::new("agtype".into(), 0, Kind::Simple, "ag_catalog".into())
+}
+
+fn decode(text: &str) -> AgValue {
+    let raw = [&[1u8], text.as_bytes()].concat();
+    AgValue::from_sql(&agtype(), &raw).unwrap()
+}
+
+#[test]
+fn scalars() {
+    assert_eq!(decode("null"), AgValue::Null);
+    assert_eq!(decode("true"), AgValue::Bool(true));
+    assert_eq!(decode("-12"), AgValue::Integer(-12));
+    assert_eq!(decode("1.5"), AgValue::Float(1.5));
+    assert_eq!(decode("2::float"), AgValue::Float(2.0));
+    assert_eq!(decode("\"a\\\"b\""), AgValue::String("a\"b".into()));
+
+    match decode("12345678901234567890.123::numeric") {
+        AgValue::Numeric(x) => assert_eq!(x.as_str(), "12345678901234567890.123"),
+        x => panic!("Expected numeric, got {:?}", x),
+    }
+}
+
+#[test]
+fn nested_entities() {
+    let value = decode(
+        r#"{"people": [{"id": 844424930131969, "label": "Person", "properties": {"name": "John"}}::vertex], "knows": {"id": 1125899906842625, "label": "Knows", "end_id": 844424930131970, "start_id": 844424930131969, "properties": {}}::edge}"#,
+    );
+
+    let map = value.as_map().unwrap();
+    let people = map["people"].as_list().unwrap();
+    let person = people[0].as_vertex().unwrap();
+    assert_eq!(person.id(), GraphId::from(844424930131969));
+    assert_eq!(person.id().label_id(), 3);
+    assert_eq!(person.id().entry_id(), 1);
+    assert_eq!(person.label(), "Person");
+    assert_eq!(person.properties()["name"].as_str(), Some("John"));
+
+    let knows = map["knows"].as_edge().unwrap();
+    assert_eq!(knows.start_id(), person.id());
+    assert_eq!(u64::from(knows.end_id()), 844424930131970);
+}
+
+#[test]
+fn path() {
+    let value = decode(
+        r#"[{"id": 1, "label": "Person", "properties": {}}::vertex, {"id": 3, "label": "ChildOf", "end_id": 2, "start_id": 1, "properties": {}}::edge, {"id": 2, "label": "Person", "properties": {}}::vertex]::path"#,
+    );
+
+    let path = value.as_path().unwrap();
+    assert_eq!(path.vertices().len(), 2);
+    assert_eq!(path.edges().len(), 1);
+}
+
+#[test]
+fn null_column() {
+    assert_eq!(AgValue::from_sql_null(&agtype()).unwrap(), AgValue::Null);
+}
+
+#[test]
+fn invalid() {
+    for text in ["{", "[1,]", "tru", "{\"a\" 1}", "1 2", "{}::vertex"] {
+        let raw = [&[1u8], text.as_bytes()].concat();
+        assert!(AgValue::from_sql(&agtype(), &raw).is_err(), "{}", text);
+    }
+}
@@ -0,0 +1,120 @@
+use apache_age::{agtype, AgValue, Vertex};
+use postgres_types::{FromSql, Kind, Type};
+use proptest::prelude::*;
+use serde::Deserialize;
+use std::borrow::Cow;
+
+#[derive(Debug, Deserialize)]
+struct Person<'a> {
+    pub name: &'a str,
+    #[serde(borrow)]
+    pub surname: Cow<'a, str>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+enum Status {
+    Active,
+    Closed { reason: String },
+}
+
+fn agtype() -> Type {
+    Type::new("agtype".into(), 0, Kind::Simple, "ag_catalog".into())
+}
+
+#[test]
+fn borrowed_strings() {
+    let text = r#"{"id": 1, "label": "Person", "properties": {"name": "John", "surname": "D\"oe"}}::vertex"#;
+    let person: Vertex<Person> = agtype::from_str(text).unwrap();
+
+    assert_eq!(person.properties().name, "John");
+    assert!(matches!(person.properties().surname, Cow::Owned(_)));
+    assert_eq!(person.properties().surname, "D\"oe");
+
+    // Row buffer can be borrowed as well
+    let raw = [&[1u8], text.replace("D\\\"oe", "Doe").as_bytes()].concat();
+    let person = Vertex::<Person>::from_sql(&agtype(), &raw).unwrap();
+    assert!(matches!(person.properties().surname, Cow::Borrowed("Doe")));
+
+    // Escaped strings cannot be borrowed
+    let text = r#"{"name": "J\nohn", "surname": "Doe"}"#;
+    assert!(agtype::from_str::<Person>(text).is_err());
+}
+
+#[test]
+fn enums() {
+    let statuses: Vec<Status> =
+        agtype::from_str(r#"["Active", {"Closed": {"reason": "done"}}]"#).unwrap();
+    assert_eq!(
+        statuses,
+        [
+            Status::Active,
+            Status::Closed {
+                reason: "done".into()
+            }
+        ]
+    );
+}
+
+#[test]
+fn annotations() {
+    let x: Vec<f64> = agtype::from_str("[1.5::numeric, 2::float, NaN]").unwrap();
+    assert_eq!(x[..2], [1.5, 2.0]);
+
+    let x: &str = agtype::from_str("10.25::numeric").unwrap();
+    assert_eq!(x, "10.25");
+
+    assert!(agtype::from_str::<Vec<i64>>("[1]::vertex").is_err());
+    assert!(agtype::from_str::<String>("\"a\"::numeric").is_err());
+
+    // Vertex has to be annotated when it is the whole column
+    let raw = [
+        &[1u8],
+        br#"{"id": 1, "label": "Person", "properties": {"name": "a", "surname": "b"}}"#.as_slice(),
+    ]
+    .concat();
+    assert!(Vertex::<Person>::from_sql(&agtype(), &raw).is_err());
+}
+
+#[test]
+fn error_offsets() {
+    let err = agtype::from_str::<Vec<i64>>("[1, 2, \"x\"]").unwrap_err();
+    assert_eq!(err.offset(), Some(7));
+
+    let err = agtype::from_str::<Vec<i64>>("[1, 2 3]").unwrap_err();
+    assert_eq!(err.offset(), Some(6));
+}
+
+fn value() -> impl Strategy<Value = AgValue> {
+    let leaf = prop_oneof![
+        Just(AgValue::Null),
+        any::<bool>().prop_map(AgValue::Bool),
+        any::<i64>().prop_map(AgValue::Integer),
+        any::<f64>()
+            .prop_filter("finite", |x| x.is_finite())
+            .prop_map(AgValue::Float),
+        any::<String>().prop_map(AgValue::String),
+    ];
+
+    leaf.prop_recursive(4, 64, 8, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..8).prop_map(AgValue::List),
+            prop::collection::btree_map(any::<String>(), inner, 0..8).prop_map(AgValue::Map),
+        ]
+    })
+}
+
+proptest! {
+    #[test]
+    fn streaming_matches_tree(value in value()) {
+        let text = value.to_string();
+        let streamed: serde_json::Value = agtype::from_str(&text).unwrap();
+        let tree = serde_json::Value::deserialize(value).unwrap();
+        prop_assert_eq!(streamed, tree);
+    }
+
+    #[test]
+    fn arbitrary_input_does_not_panic(data in prop::collection::vec(any::<u8>(), 0..256)) {
+        let _ = agtype::from_slice::<serde_json::Value>(&data);
+        let _ = agtype::from_slice::<Vec<Vertex<serde::de::IgnoredAny>>>(&data);
+    }
+}
@@ -0,0 +1,212 @@
+use apache_age::{AgType, AgValue, Edge, GraphId, Path, Vertex};
+use postgres_types::{FromSql, Kind, Type};
+use proptest::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct Person {
+    pub name: String,
+    pub surname: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct ChildOf {
+    pub surname: String,
+}
+
+fn agtype() -> Type {
+    Type::new("agtype".into(), 0, Kind::Simple, "ag_catalog".into())
+}
+
+fn raw(text: &str) -> Vec<u8> {
+    [&[1u8], text.as_bytes()].concat()
+}
+
+fn vertex_text(id: u64, person: &Person) -> String {
+    format!(
+        "{{\"id\": {}, \"label\": \"Person\", \"properties\": {}}}::vertex",
+        id,
+        serde_json::to_string(person).unwrap()
+    )
+}
+
+fn edge_text(id: u64, start_id: u64, end_id: u64, child_of: &ChildOf) -> String {
+    format!(
+        "{{\"id\": {}, \"label\": \"ChildOf\", \"end_id\": {}, \"start_id\": {}, \"properties\": {}}}::edge",
+        id,
+        end_id,
+        start_id,
+        serde_json::to_string(child_of).unwrap()
+    )
+}
+
+#[test]
+fn path_with_annotations_in_strings() {
+    let jane = Person {
+        name: "Jane ::vertex {".into(),
+        surname: "}::edge \"[".into(),
+    };
+    let john = Person {
+        name: "John::path".into(),
+        surname: "\\::vertex".into(),
+    };
+    let child_of = ChildOf {
+        surname: "{\"id\": 1}::edge".into(),
+    };
+
+    let text = format!(
+        "[{}, {}, {}]::path",
+        vertex_text(1, &jane),
+        edge_text(3, 1, 2, &child_of),
+        vertex_text(2, &john)
+    );
+
+    let path = Path::<Person, ChildOf>::from_sql(&agtype(), &raw(&text)).unwrap();
+    assert_eq!(path.vertices()[0].properties(), &jane);
+    assert_eq!(path.vertices()[1].properties(), &john);
+    assert_eq!(path.edges()[0].properties(), &child_of);
+    assert_eq!(path.edges()[0].start_id(), GraphId::from(1));
+}
+
+#[test]
+fn escapes() {
+    let value: AgValue = r#""A\n\t\/😀""#.parse().unwrap();
+    assert_eq!(value.as_str(), Some("A\n\t/\u{1F600}"));
+
+    for invalid in [r#""\ud83d""#, r#""\x""#, r#""\u12""#, "\"\u{1}\""] {
+        assert!(invalid.parse::<AgValue>().is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn truncated_inputs() {
+    let text = format!(
+        "[{}, {}, {}]::path",
+        vertex_text(
+            1,
+            &Person {
+                name: "a".into(),
+                surname: "b".into()
+            }
+        ),
+        edge_text(
+            3,
+            1,
+            2,
+            &ChildOf {
+                surname: "b".into()
+            }
+        ),
+        vertex_text(
+            2,
+            &Person {
+                name: "c".into(),
+                surname: "d".into()
+            }
+        ),
+    );
+
+    for i in 0..text.len() {
+        let raw = raw(&text[..i]);
+        assert!(Path::<Person, ChildOf>::from_sql(&agtype(), &raw).is_err());
+    }
+}
+
+#[test]
+fn wrong_entity() {
+    let text = vertex_text(
+        1,
+        &Person {
+            name: "a".into(),
+            surname: "b".into(),
+        },
+    );
+    assert!(Edge::<ChildOf>::from_sql(&agtype(), &raw(&text)).is_err());
+    assert!(Path::<Person, ChildOf>::from_sql(&agtype(), &raw(&text)).is_err());
+    assert!(Vertex::<Person>::from_sql(&agtype(), &raw(&text)).is_ok());
+}
+
+#[test]
+fn recursion_limit() {
+    let text = "[".repeat(100_000);
+    assert!(AgValue::from_sql(&agtype(), &raw(&text)).is_err());
+}
+
+fn key() -> impl Strategy<Value = String> {
+    prop_oneof![
+        any::<String>(),
+        "[{}\\[\\]\":,\\\\]{0,8}",
+        Just("::vertex".to_string()),
+        Just("::edge".to_string()),
+    ]
+}
+
+fn value() -> impl Strategy<Value = AgValue> {
+    let leaf = prop_oneof![
+        Just(AgValue::Null),
+        any::<bool>().prop_map(AgValue::Bool),
+        any::<i64>().prop_map(AgValue::Integer),
+        any::<f64>()
+            .prop_filter("finite", |x| x.is_finite())
+            .prop_map(AgValue::Float),
+        key().prop_map(AgValue::String),
+    ];
+
+    leaf.prop_recursive(4, 64, 8, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..8).prop_map(AgValue::List),
+            prop::collection::btree_map(key(), inner, 0..8).prop_map(AgValue::Map),
+        ]
+    })
+}
+
+fn person() -> impl Strategy<Value = Person> {
+    (key(), key()).prop_map(|(name, surname)| Person { name, surname })
+}
+
+proptest! {
+    #[test]
+    fn value_round_trip(value in value()) {
+        let text = value.to_string();
+        prop_assert_eq!(text.parse::<AgValue>().unwrap(), value);
+    }
+
+    #[test]
+    fn typed_values(value in prop::collection::vec(person(), 0..4)) {
+        let text = serde_json::to_string(&value).unwrap();
+        let decoded = AgType::<Vec<Person>>::from_sql(&agtype(), &raw(&text)).unwrap();
+        prop_assert_eq!(decoded.0, value);
+    }
+
+    #[test]
+    fn path_round_trip(jane in person(), john in person(), surname in key(), id in 0..u32::MAX as u64) {
+        let child_of = ChildOf { surname };
+        let text = format!(
+            "[{}, {}, {}]::path",
+            vertex_text(id, &jane),
+            edge_text(id + 2, id, id + 1, &child_of),
+            vertex_text(id + 1, &john)
+        );
+
+        let path = Path::<Person, ChildOf>::from_sql(&agtype(), &raw(&text)).unwrap();
+        prop_assert_eq!(path.vertices()[0].properties(), &jane);
+        prop_assert_eq!(path.vertices()[1].properties(), &john);
+        prop_assert_eq!(path.edges()[0].properties(), &child_of);
+        prop_assert_eq!(path.edges()[0].end_id(), path.vertices()[1].id());
+    }
+
+    #[test]
+    fn arbitrary_input_does_not_panic(data in prop::collection::vec(any::<u8>(), 0..256)) {
+        let raw = [&[1u8], data.as_slice()].concat();
+        let _ = AgValue::from_sql(&agtype(), &raw);
+        let _ = Vertex::<serde_json::Value>::from_sql(&agtype(), &raw);
+        let _ = Path::<serde_json::Value, serde_json::Value>::from_sql(&agtype(), &raw);
+    }
+
+    #[test]
+    fn token_soup_does_not_panic(text in "[\\[\\]{}\":,0-9a-z\\\\ .e+-]{0,64}") {
+        let raw = raw(&text);
+        let _ = AgValue::from_sql(&agtype(), &raw);
+        let _ = AgType::<serde_json::Value>::from_sql(&agtype(), &raw);
+    }
+}
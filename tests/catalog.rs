@@ -0,0 +1,36 @@
+use apache_age::{Error, GraphStats, LabelKind, LabelStats};
+
+fn label(name: &str, kind: LabelKind, count: i64) -> LabelStats {
+    LabelStats {
+        name: name.to_string(),
+        kind,
+        count,
+    }
+}
+
+#[test]
+fn graph_stats_totals() {
+    let stats = GraphStats {
+        labels: vec![
+            label("_ag_label_vertex", LabelKind::Vertex, 1),
+            label("_ag_label_edge", LabelKind::Edge, 0),
+            label("Person", LabelKind::Vertex, 10),
+            label("City", LabelKind::Vertex, 3),
+            label("LIVES_IN", LabelKind::Edge, 10),
+        ],
+    };
+
+    assert_eq!(stats.vertex_count(), 14);
+    assert_eq!(stats.edge_count(), 10);
+    assert_eq!(GraphStats { labels: vec![] }.vertex_count(), 0);
+}
+
+#[test]
+fn label_kinds() {
+    assert_eq!("v".parse::<LabelKind>().unwrap(), LabelKind::Vertex);
+    assert_eq!("e".parse::<LabelKind>().unwrap(), LabelKind::Edge);
+
+    let e = "x".parse::<LabelKind>().unwrap_err();
+    assert!(matches!(e, Error::Decode { .. }));
+    assert!(e.to_string().contains("\"x\""));
+}
@@ -0,0 +1,113 @@
+use apache_age::{AgeConfig, Error, SearchPath};
+use std::time::Duration;
+
+const CONN: &str = "host=localhost user=postgres password=passwd port=8081";
+
+#[test]
+fn default_setup() {
+    let config = AgeConfig::from(CONN);
+
+    assert_eq!(
+        config.setup_query(),
+        "LOAD 'age'; SET search_path = ag_catalog, \"$user\", public"
+    );
+    assert_eq!(
+        config.session_query(),
+        "SET search_path = ag_catalog, \"$user\", public"
+    );
+
+    let pg = config.pg_config().unwrap();
+    assert_eq!(pg.get_user(), Some("postgres"));
+    assert_eq!(pg.get_ports(), [8081]);
+}
+
+#[test]
+fn session_options() {
+    let config = AgeConfig::new(CONN)
+        .load_age(false)
+        .search_path(SearchPath::Keep)
+        .statement_timeout(Duration::from_millis(1500))
+        .session_sql("SET work_mem = '64MB'")
+        .session_sql("SET lock_timeout = 100");
+
+    assert_eq!(
+        config.setup_query(),
+        "SET statement_timeout = 1500; SET work_mem = '64MB'; SET lock_timeout = 100"
+    );
+
+    let config = AgeConfig::new(CONN).search_path(SearchPath::Keep);
+    assert_eq!(config.setup_query(), "LOAD 'age'");
+    assert_eq!(config.session_query(), "");
+
+    let prepend = AgeConfig::new(CONN).search_path(SearchPath::Prepend);
+    assert!(prepend
+        .session_query()
+        .contains("'ag_catalog, ' || current_setting('search_path')"));
+
+    let append = AgeConfig::new(CONN).search_path(SearchPath::Append);
+    assert!(append
+        .session_query()
+        .contains("current_setting('search_path') || ', ag_catalog'"));
+}
+
+#[test]
+fn application_name() {
+    let config = AgeConfig::new(format!("{} application_name=first", CONN));
+    assert_eq!(
+        config.pg_config().unwrap().get_application_name(),
+        Some("first")
+    );
+
+    let config = config.application_name("second");
+    assert_eq!(
+        config.pg_config().unwrap().get_application_name(),
+        Some("second")
+    );
+
+    let mut pg = tokio_postgres::Config::new();
+    pg.user("postgres");
+    let config = AgeConfig::from(pg).application_name("third");
+    let pg = config.pg_config().unwrap();
+    assert_eq!(pg.get_user(), Some("postgres"));
+    assert_eq!(pg.get_application_name(), Some("third"));
+}
+
+#[test]
+fn invalid_params() {
+    let config = AgeConfig::new("host=localhost port=not_a_port");
+
+    assert!(matches!(config.pg_config(), Err(Error::Connection(_))));
+}
+
+#[test]
+fn from_env() {
+    std::env::set_var("PGHOST", "db.example.com");
+    std::env::set_var("PGPORT", "5433");
+    std::env::set_var("PGUSER", "o'neil");
+    std::env::set_var("PGPASSWORD", "pass \\ word");
+    std::env::set_var("PGDATABASE", "graphs");
+    std::env::set_var("PGAPPNAME", "");
+
+    let pg = AgeConfig::from_env().pg_config().unwrap();
+
+    assert_eq!(pg.get_ports(), [5433]);
+    assert_eq!(pg.get_user(), Some("o'neil"));
+    assert_eq!(pg.get_password(), Some("pass \\ word".as_bytes()));
+    assert_eq!(pg.get_dbname(), Some("graphs"));
+    assert_eq!(pg.get_application_name(), Some(""));
+}
+
+#[test]
+fn debug_hides_password() {
+    let config = AgeConfig::new("host=localhost user=postgres password=s3cr3t port=8081");
+    let debug = format!("{:?}", config);
+    assert!(debug.contains("postgres"));
+    assert!(!debug.contains("s3cr3t"));
+
+    let config = AgeConfig::new("host=localhost password='s3cr3t port=8081");
+    assert!(!format!("{:?}", config).contains("s3cr3t"));
+
+    let mut pg = tokio_postgres::Config::new();
+    pg.user("postgres").password("s3cr3t");
+    assert!(!format!("{:?}", AgeConfig::from(pg)).contains("s3cr3t"));
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "tokio")]
+
+use apache_age::tokio::{AgeClient, Client};
+use apache_age::{AgeConfig, Error, NoTls, SearchPath};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Reads a single message of the frontend protocol (startup message has no type byte)
+async fn read_message(socket: &mut TcpStream, typed: bool) {
+    if typed {
+        socket.read_u8().await.unwrap();
+    }
+    let len = socket.read_i32().await.unwrap() as usize;
+    let mut body = vec![0; len - 4];
+    socket.read_exact(&mut body).await.unwrap();
+}
+
+/// Accepts the connection, answers the startup and a single query, then drops the socket
+async fn flaky_server(listener: TcpListener) {
+    let (mut socket, _) = listener.accept().await.unwrap();
+    let ready_for_query = [b'Z', 0, 0, 0, 5, b'I'];
+
+    read_message(&mut socket, false).await;
+    socket
+        .write_all(&[b'R', 0, 0, 0, 8, 0, 0, 0, 0])
+        .await
+        .unwrap();
+    socket.write_all(&ready_for_query).await.unwrap();
+
+    read_message(&mut socket, true).await;
+    socket.write_all(&[b'I', 0, 0, 0, 4]).await.unwrap();
+    socket.write_all(&ready_for_query).await.unwrap();
+}
+
+#[tokio::test]
+async fn connection_errors_are_surfaced() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let server = tokio::spawn(flaky_server(listener));
+
+    let errors = Arc::new(AtomicUsize::new(0));
+    let counter = errors.clone();
+    let config = AgeConfig::new(format!("host=127.0.0.1 port={} user=postgres", port))
+        .load_age(false)
+        .search_path(SearchPath::Keep)
+        .on_connection_error(move |e| {
+            assert!(matches!(e, Error::Connection(_)));
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+    let (client, handle) = Client::connect_age(config, NoTls).await.unwrap();
+    server.await.unwrap();
+
+    let result = handle.await.unwrap();
+    assert!(matches!(result, Err(Error::Connection(_))));
+    assert_eq!(errors.load(Ordering::SeqCst), 1);
+    assert!(client.is_closed());
+}
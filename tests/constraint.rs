@@ -0,0 +1,107 @@
+use apache_age::{Constraint, Error, PropertyType};
+
+#[test]
+fn typed_checks() {
+    let constraint = Constraint::new("person_check")
+        .required("name")
+        .of_type("name", PropertyType::String)
+        .of_type("age", PropertyType::Int)
+        .of_type("active", PropertyType::Bool);
+
+    assert_eq!(
+        constraint.to_sql("graph", "Person").unwrap(),
+        "ALTER TABLE \"graph\".\"Person\" ADD CONSTRAINT \"person_check\" CHECK(\
+         agtype_access_operator(properties, '\"name\"') IS NOT NULL \
+         AND left((agtype_access_operator(properties, '\"name\"'))::text, 1) = '\"' \
+         AND (agtype_access_operator(properties, '\"age\"'))::text ~ '^-?[0-9]+$' \
+         AND (agtype_access_operator(properties, '\"active\"'))::text IN ('true', 'false'))"
+    );
+}
+
+#[test]
+fn property_paths() {
+    let expression = Constraint::new("zip")
+        .required("address.zip")
+        .expression()
+        .unwrap();
+    assert_eq!(
+        expression,
+        "agtype_access_operator(properties, '\"address\"', '\"zip\"') IS NOT NULL"
+    );
+}
+
+#[test]
+fn value_checks() {
+    let expression = Constraint::new("status")
+        .one_of("status", ["active", "it's banned"])
+        .expression()
+        .unwrap();
+    assert_eq!(
+        expression,
+        "agtype_access_operator(properties, '\"status\"') IN \
+         ('\"active\"'::agtype, '\"it''s banned\"'::agtype)"
+    );
+
+    let expression = Constraint::new("age")
+        .range("age", 0.0..150.5)
+        .expression()
+        .unwrap();
+    assert!(expression.starts_with("COALESCE("));
+    assert!(expression.contains(" >= 0 AND "));
+    assert!(expression.contains(" < 150.5, "));
+
+    let expression = Constraint::new("email")
+        .matches("email", "^[^@]+@[^@']+$")
+        .length("email", ..=255)
+        .expression()
+        .unwrap();
+    assert!(expression.contains(" ~ '^[^@]+@[^@'']+$', "));
+    assert!(expression.contains(" <= 255, "));
+}
+
+#[test]
+fn numeric_range() {
+    let text = "(agtype_access_operator(properties, '\"price\"'))::text";
+    let number = format!(
+        "(CASE WHEN {0} ~ '^-?[0-9]+(\\.[0-9]+)?(e[-+]?[0-9]+)?(::numeric)?$' \
+         THEN regexp_replace({0}, '::numeric$', '')::numeric END)",
+        text
+    );
+
+    assert_eq!(
+        Constraint::new("price")
+            .range("price", 0.0..)
+            .expression()
+            .unwrap(),
+        format!(
+            "COALESCE({} >= 0, agtype_access_operator(properties, '\"price\"') IS NULL)",
+            number
+        )
+    );
+}
+
+#[test]
+fn invalid_constraints() {
+    assert!(matches!(
+        Constraint::new("empty").to_sql("graph", "Person"),
+        Err(Error::InvalidSchema(_))
+    ));
+    assert!(matches!(
+        Constraint::new("range")
+            .range("age", f64::NAN..)
+            .expression(),
+        Err(Error::InvalidSchema(_))
+    ));
+    assert!(matches!(
+        Constraint::new("values")
+            .one_of("status", Vec::<String>::new())
+            .expression(),
+        Err(Error::InvalidSchema(_))
+    ));
+    assert!(matches!(
+        Constraint::new("check")
+            .required("name")
+            .to_sql("graph", "Person\" CASCADE"),
+        Err(Error::InvalidIdentifier(_))
+    ));
+}
@@ -0,0 +1,128 @@
+use apache_age::{params, CypherParams, Error};
+use postgres_types::{Kind, ToSql, Type};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Serialize)]
+struct Person {
+    name: String,
+    age: i64,
+}
+
+fn agtype() -> Type {
+    Type::new("agtype".into(), 0, Kind::Simple, "ag_catalog".into())
+}
+
+fn invalid(result: Result<(), Error>) -> (Vec<String>, Vec<String>) {
+    match result {
+        Err(Error::InvalidParameters { missing, unused }) => (missing, unused),
+        other => panic!("Expected invalid parameters, got {:?}", other),
+    }
+}
+
+#[test]
+fn matching_placeholders() {
+    let params = params! { name: "John", age: 42 };
+
+    assert!(params
+        .validate("MATCH (n: Person {name: $name}) SET n.age = $age RETURN n")
+        .is_ok());
+    assert!(params
+        .validate("MATCH (n {name: $name}) WHERE n.age > $age OR n.age < $age RETURN n")
+        .is_ok());
+    assert!(params! {}.validate("MATCH (n) RETURN n").is_ok());
+}
+
+#[test]
+fn missing_and_unused() {
+    let params = params! { name: "John", age: 42, surname: "Doe" };
+    let (missing, unused) =
+        invalid(params.validate("CREATE (n: Person {name: $name, age: $agee, id: $id})"));
+
+    assert_eq!(missing, ["agee", "id"]);
+    assert_eq!(unused, ["age", "surname"]);
+
+    let e = params.validate("CREATE (n {name: $nmae})").unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "Invalid cypher parameters, missing: nmae, unused: age, name, surname"
+    );
+}
+
+#[test]
+fn literals_are_skipped() {
+    let params = params! { name: "John" };
+
+    assert!(params
+        .validate("CREATE (n {name: $name, note: 'costs $price', `$weird`: \"$x\"}) // $comment")
+        .is_ok());
+    assert!(params
+        .validate("CREATE (n {name: $name}) /* $first\n $second */ RETURN n")
+        .is_ok());
+}
+
+#[test]
+fn from_serialize() {
+    let params = CypherParams::from_serialize(&Person {
+        name: "John".into(),
+        age: 42,
+    })
+    .unwrap();
+    assert_eq!(params.keys().collect::<Vec<_>>(), ["age", "name"]);
+    assert!(params.validate("CREATE ({name: $name, age: $age})").is_ok());
+
+    let map: BTreeMap<&str, i64> = [("id", 1)].into();
+    assert!(CypherParams::from_serialize(&map).is_ok());
+
+    for result in [
+        CypherParams::from_serialize(&[1, 2]),
+        CypherParams::from_serialize("name"),
+        CypherParams::from_serialize(&42),
+    ] {
+        assert!(matches!(result, Err(Error::Serialization(_))));
+    }
+}
+
+#[test]
+fn serialization_errors() {
+    let key: BTreeMap<(i64, i64), i64> = [((1, 2), 3)].into();
+    let params = params! { name: "John", key: key };
+
+    assert!(matches!(
+        params.validate("CREATE ({name: $name, key: $key})"),
+        Err(Error::Serialization(_))
+    ));
+
+    let mut out = bytes::BytesMut::new();
+    assert!(params.to_sql(&agtype(), &mut out).is_err());
+}
+
+#[test]
+fn non_finite_floats() {
+    for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        let params = params! { score: value };
+        assert!(matches!(
+            params.validate("CREATE ({score: $score})"),
+            Err(Error::Serialization(_))
+        ));
+    }
+
+    let map: BTreeMap<&str, Vec<f32>> = [("scores", vec![1.5, f32::NAN])].into();
+    assert!(matches!(
+        CypherParams::from_serialize(&map),
+        Err(Error::Serialization(_))
+    ));
+    assert!(params! { score: 1.5 }.validate("RETURN $score").is_ok());
+}
+
+#[test]
+fn encoded_as_agtype_map() {
+    let params = CypherParams::new()
+        .param("name", "John")
+        .param("tags", ["a", "b"]);
+    let mut out = bytes::BytesMut::new();
+
+    params.to_sql(&agtype(), &mut out).unwrap();
+
+    assert_eq!(&out[..], b"\x01{\"name\":\"John\",\"tags\":[\"a\",\"b\"]}");
+}
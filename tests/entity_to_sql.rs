@@ -0,0 +1,71 @@
+use apache_age::{AgValue, Edge, Path, Vertex};
+use bytes::BytesMut;
+use postgres_types::{FromSql, Kind, ToSql, Type};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct Person {
+    pub name: String,
+    pub surname: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct ChildOf {
+    pub surname: String,
+}
+
+fn agtype() -> Type {
+    Type::new("agtype".into(), 0, Kind::Simple, "ag_catalog".into())
+}
+
+fn raw(text: &str) -> Vec<u8> {
+    [&[1u8], text.as_bytes()].concat()
+}
+
+fn encode(value: &dyn ToSql) -> BytesMut {
+    let mut out = BytesMut::new();
+    value.to_sql_checked(&agtype(), &mut out).unwrap();
+    out
+}
+
+const PATH: &str = r#"[{"id": 844424930131969, "label": "Person", "properties": {"name": "Jane", "surname": "O'Doe \"::vertex"}}::vertex, {"id": 1125899906842625, "label": "ChildOf", "end_id": 844424930131970, "start_id": 844424930131969, "properties": {"surname": "Doe"}}::edge, {"id": 844424930131970, "label": "Person", "properties": {"name": "John", "surname": "Doe"}}::vertex]::path"#;
+
+#[test]
+fn path_round_trip() {
+    let path = Path::<Person, ChildOf>::from_sql(&agtype(), &raw(PATH)).unwrap();
+    let encoded = encode(&path);
+    assert_eq!(encoded[0], 1);
+    assert_eq!(
+        Path::<Person, ChildOf>::from_sql(&agtype(), &encoded).unwrap(),
+        path
+    );
+}
+
+#[test]
+fn entities_round_trip() {
+    let path = Path::<Person, ChildOf>::from_sql(&agtype(), &raw(PATH)).unwrap();
+
+    let vertex = &path.vertices()[0];
+    let encoded = encode(vertex);
+    assert!(encoded.ends_with(b"}::vertex"));
+    assert_eq!(
+        &Vertex::<Person>::from_sql(&agtype(), &encoded).unwrap(),
+        vertex
+    );
+
+    let edge = &path.edges()[0];
+    let encoded = encode(edge);
+    assert!(encoded.ends_with(b"}::edge"));
+    assert_eq!(
+        &Edge::<ChildOf>::from_sql(&agtype(), &encoded).unwrap(),
+        edge
+    );
+}
+
+#[test]
+fn value_round_trip() {
+    let value = AgValue::from_sql(&agtype(), &raw(PATH)).unwrap();
+    let encoded = encode(&value);
+    assert_eq!(&encoded[1..], PATH.as_bytes());
+    assert_eq!(AgValue::from_sql(&agtype(), &encoded).unwrap(), value);
+}
@@ -0,0 +1,87 @@
+use apache_age::{agtype, AgType, AgValue, DecodeError, Error, GraphId, Path, Vertex};
+use postgres_types::{FromSql, Kind, ToSql, Type};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Person {
+    pub name: String,
+}
+
+fn agtype() -> Type {
+    Type::new("agtype".into(), 0, Kind::Simple, "ag_catalog".into())
+}
+
+fn raw(text: &str) -> Vec<u8> {
+    [&[1u8], text.as_bytes()].concat()
+}
+
+fn decode_error(e: Box<dyn std::error::Error + Sync + Send>) -> Error {
+    Error::from(*e.downcast::<DecodeError>().unwrap())
+}
+
+#[test]
+fn decode_errors() {
+    let e = Vertex::<Person>::from_sql(&agtype(), &raw("[1, 2]::path")).unwrap_err();
+    match decode_error(e) {
+        Error::Decode {
+            offset, expected, ..
+        } => {
+            assert!(offset.is_some());
+            assert!(expected.unwrap().contains("Vertex<error::Person>"));
+        }
+        e => panic!("Expected decode error, got {:?}", e),
+    }
+
+    let e = AgType::<Vec<i64>>::from_sql(&agtype(), &raw("[1, \"a\"]")).unwrap_err();
+    match decode_error(e) {
+        Error::Decode {
+            offset,
+            expected,
+            message,
+        } => {
+            assert_eq!(offset, Some(4));
+            assert!(expected.unwrap().contains("AgType<alloc::vec::Vec<i64>>"));
+            assert!(message.contains("expected i64"), "{}", message);
+        }
+        e => panic!("Expected decode error, got {:?}", e),
+    }
+
+    let e = AgValue::from_sql(&agtype(), &[2, b'1']).unwrap_err();
+    let e = decode_error(e);
+    assert_eq!(
+        e.to_string(),
+        "Cannot decode agtype into apache_age::value::AgValue: Unsupported JSONB encoding version at byte 0"
+    );
+
+    let e = GraphId::from_sql(&Type::INT8, &[0; 4]).unwrap_err();
+    assert!(matches!(decode_error(e), Error::Decode { .. }));
+
+    let e: Error = agtype::from_str::<Person>("{\"name\": 1}")
+        .unwrap_err()
+        .into();
+    assert!(matches!(e, Error::Decode { expected: None, .. }));
+}
+
+#[test]
+fn serialization_errors() {
+    let path: Path<Person, Person> = agtype::from_str(r#"{"vertices": [], "edges": []}"#).unwrap();
+    match path.to_sql(&agtype(), &mut bytes::BytesMut::new()) {
+        Err(e) => assert!(matches!(
+            e.downcast_ref::<Error>(),
+            Some(Error::Serialization(_))
+        )),
+        Ok(_) => panic!("Path without vertices must not be serialized"),
+    }
+}
+
+#[cfg(feature = "serializers")]
+#[test]
+fn serializer_errors() {
+    use std::collections::HashMap;
+
+    let map = HashMap::from([(vec![1], 1)]);
+    assert!(matches!(
+        apache_age::serializers::literal::to_string(&map),
+        Err(Error::Serialization(_))
+    ));
+}
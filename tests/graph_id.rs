@@ -0,0 +1,37 @@
+use apache_age::GraphId;
+use bytes::BytesMut;
+use postgres_types::{FromSql, Kind, ToSql, Type};
+
+fn graphid() -> Type {
+    Type::new("graphid".into(), 0, Kind::Simple, "ag_catalog".into())
+}
+
+#[test]
+fn parts() {
+    let id = GraphId::from(844424930131969);
+    assert_eq!(id.label_id(), 3);
+    assert_eq!(id.entry_id(), 1);
+    assert_eq!(GraphId::new(3, 1), id);
+    assert_eq!(GraphId::new(u16::MAX, u64::MAX).entry_id(), (1 << 48) - 1);
+}
+
+#[test]
+fn text() {
+    let id: GraphId = "1125899906842625".parse().unwrap();
+    assert_eq!(id.to_string(), "1125899906842625");
+    assert_eq!(id.label_id(), 4);
+    assert!("-1".parse::<GraphId>().is_err());
+}
+
+#[test]
+fn sql_round_trip() {
+    let id = GraphId::new(7, 42);
+    let mut out = BytesMut::new();
+    id.to_sql_checked(&graphid(), &mut out).unwrap();
+    assert_eq!(out.len(), 8);
+    assert_eq!(GraphId::from_sql(&graphid(), &out).unwrap(), id);
+
+    assert!(GraphId::from_sql(&graphid(), &[0, 1]).is_err());
+    assert!(<GraphId as ToSql>::accepts(&Type::INT8));
+    assert!(!<GraphId as ToSql>::accepts(&Type::TEXT));
+}
@@ -0,0 +1,73 @@
+use apache_age::{Error, GraphName, LabelKind, LabelName};
+
+#[test]
+fn graph_names() {
+    for name in [
+        "abc",
+        "social_graph",
+        "_graph",
+        "graph.v2",
+        "my-graph",
+        "gräph",
+    ] {
+        assert_eq!(GraphName::new(name).unwrap().as_str(), name);
+    }
+
+    for name in [
+        "",
+        "ab",
+        "1graph",
+        "graph-",
+        "graph.",
+        "gr aph",
+        "graph'; DROP TABLE users; --",
+        "graph\"",
+        "graph$$",
+        &"g".repeat(64),
+    ] {
+        match GraphName::new(name) {
+            Err(Error::InvalidIdentifier(x)) => assert_eq!(x, name),
+            other => panic!("{:?} accepted as {:?}", name, other),
+        }
+    }
+}
+
+#[test]
+fn label_names() {
+    for name in ["P", "Person", "_internal", "Knows2"] {
+        assert!(LabelName::new(name).is_ok());
+    }
+
+    for name in [
+        "",
+        "2Person",
+        "Person\" CASCADE",
+        "Per-son",
+        "Per.son",
+        "Person;",
+    ] {
+        assert!(matches!(
+            LabelName::new(name),
+            Err(Error::InvalidIdentifier(_))
+        ));
+    }
+}
+
+#[test]
+fn conversions() {
+    let graph: GraphName = "graph".parse().unwrap();
+    let label = LabelName::try_from("Person".to_string()).unwrap();
+
+    assert_eq!(graph.to_string(), "graph");
+    assert_eq!(&*label, "Person");
+    assert!("1graph".parse::<GraphName>().is_err());
+    assert!(LabelName::try_from("").is_err());
+}
+
+#[test]
+fn label_kinds() {
+    assert_eq!("v".parse::<LabelKind>().unwrap(), LabelKind::Vertex);
+    assert_eq!("e".parse::<LabelKind>().unwrap(), LabelKind::Edge);
+    assert!("x".parse::<LabelKind>().is_err());
+    assert_eq!(LabelKind::Edge.to_string(), "edge");
+}
@@ -0,0 +1,84 @@
+use apache_age::{Error, Index, IndexKey, IndexMethod};
+
+#[test]
+fn property_indexes() {
+    let index = Index::new("person_name_idx")
+        .property("surname")
+        .property("name");
+    assert_eq!(
+        index.to_sql("graph", "Person").unwrap(),
+        "CREATE INDEX \"person_name_idx\" ON \"graph\".\"Person\" USING btree \
+         (agtype_access_operator(properties, '\"surname\"'), \
+         agtype_access_operator(properties, '\"name\"'))"
+    );
+
+    let index = Index::new("email idx").property("e'mail").unique(true);
+    assert_eq!(
+        index.to_sql("graph", "Person").unwrap(),
+        "CREATE UNIQUE INDEX \"email idx\" ON \"graph\".\"Person\" USING btree \
+         (agtype_access_operator(properties, '\"e''mail\"'))"
+    );
+
+    let index = Index::new("city_idx").property("address.city");
+    assert!(index
+        .to_sql("graph", "Person")
+        .unwrap()
+        .ends_with("(agtype_access_operator(properties, '\"address\"', '\"city\"'))"));
+}
+
+#[test]
+fn gin_and_edge_indexes() {
+    assert_eq!(
+        Index::gin("props").to_sql("graph", "Person").unwrap(),
+        "CREATE INDEX \"props\" ON \"graph\".\"Person\" USING gin (properties)"
+    );
+
+    let index = Index::new("knows_idx").start_id().end_id();
+    assert_eq!(index.keys(), [IndexKey::StartId, IndexKey::EndId]);
+    assert_eq!(
+        index.to_sql("graph", "KNOWS").unwrap(),
+        "CREATE INDEX \"knows_idx\" ON \"graph\".\"KNOWS\" USING btree (start_id, end_id)"
+    );
+
+    let index = Index::new("ids")
+        .key(IndexKey::Id)
+        .method(IndexMethod::BTree);
+    assert!(index
+        .to_sql("graph", "KNOWS")
+        .unwrap()
+        .ends_with("USING btree (id)"));
+}
+
+#[test]
+fn invalid_keys() {
+    assert!(matches!(
+        Index::new("idx").to_sql("graph", "Person"),
+        Err(Error::InvalidSchema(_))
+    ));
+    assert!(matches!(
+        Index::gin("idx")
+            .property("name")
+            .start_id()
+            .to_sql("graph", "Person"),
+        Err(Error::InvalidSchema(_))
+    ));
+    assert!(matches!(
+        Index::new("idx")
+            .method(IndexMethod::Gin)
+            .to_sql("graph", "Person"),
+        Err(Error::InvalidSchema(_))
+    ));
+}
+
+#[test]
+fn invalid_names() {
+    let index = Index::new("idx").property("name");
+    assert!(matches!(
+        index.to_sql("graph", "Person\" CASCADE"),
+        Err(Error::InvalidIdentifier(_))
+    ));
+    assert!(matches!(
+        Index::new("").property("name").to_sql("graph", "Person"),
+        Err(Error::InvalidIdentifier(_))
+    ));
+}
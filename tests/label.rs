@@ -0,0 +1,159 @@
+#![cfg(all(feature = "sync", feature = "derive"))]
+#![allow(unused_must_use)]
+
+use apache_age::sync::{AgeClient, Client};
+use apache_age::{AgType, AgeLabel, FromAgRow, Label, LabelKind, NoTls};
+use rand::{distributions::Alphanumeric, Rng};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, AgeLabel)]
+struct Person {
+    #[age_label(required)]
+    name: String,
+    #[age_label(required, unique)]
+    email: String,
+    #[age_label(unique, rename = "nickName")]
+    nick: Option<String>,
+}
+
+#[derive(Serialize, AgeLabel)]
+#[age_label(label = "KNOWS", edge)]
+struct Knows {
+    #[age_label(required)]
+    since: i64,
+}
+
+/// Row of `MATCH (n: Person) RETURN n.name, n.email` bound to the label as well
+#[derive(AgeLabel, FromAgRow)]
+#[age_label(label = "Person")]
+struct PersonRow {
+    #[age_label(required)]
+    #[age(rename = "n.name")]
+    name: AgType<String>,
+    #[age_label(unique, rename = "mail")]
+    #[age(rename = "n.email")]
+    email: AgType<String>,
+}
+
+#[test]
+fn derived_labels() {
+    assert_eq!(Person::LABEL, "Person");
+    assert_eq!(Person::KIND, LabelKind::Vertex);
+    assert_eq!(Person::REQUIRED, ["name", "email"]);
+    assert_eq!(Person::UNIQUE, ["email", "nickName"]);
+    assert_eq!(Person::unique_index_name("email"), "Person_email_unique");
+
+    assert_eq!(Knows::LABEL, "KNOWS");
+    assert_eq!(Knows::KIND, LabelKind::Edge);
+    assert_eq!(Knows::REQUIRED, ["since"]);
+    assert!(Knows::UNIQUE.is_empty());
+
+    assert_eq!(PersonRow::LABEL, "Person");
+    assert_eq!(PersonRow::REQUIRED, ["name"]);
+    assert_eq!(PersonRow::UNIQUE, ["mail"]);
+
+    let row = PersonRow {
+        name: AgType("John".to_string()),
+        email: AgType("john@example.com".to_string()),
+    };
+    assert_eq!(row.name.0, "John");
+    assert_eq!(row.email.0, "john@example.com");
+}
+
+#[derive(AgeLabel)]
+#[age_label(label = "VeryLongLabelNameOfTheVerticesStoredWithinTheGraph")]
+struct LongNames {
+    #[age_label(required, unique, rename = "veryLongPropertyNameOfTheVertex")]
+    _value: String,
+}
+
+#[test]
+fn long_schema_names() {
+    let index = LongNames::unique_index_name("veryLongPropertyNameOfTheVertex");
+    let constraint = LongNames::required_constraint_name("veryLongPropertyNameOfTheVertex");
+
+    assert_eq!(index.len(), 63);
+    assert_eq!(constraint.len(), 63);
+    assert!(index.starts_with("VeryLongLabelNameOfTheVerticesStoredWithin"));
+    assert_ne!(index, constraint);
+    assert_ne!(
+        index,
+        LongNames::unique_index_name("veryLongPropertyNameOfTheEdge")
+    );
+    // Stable between the runs
+    assert_eq!(
+        index,
+        LongNames::unique_index_name("veryLongPropertyNameOfTheVertex")
+    );
+    assert_eq!(LongNames::indexes()[0].name(), index);
+    assert_eq!(LongNames::constraints()[0].name(), constraint);
+}
+
+#[test]
+fn schema_sql() {
+    let constraints = Person::constraints()
+        .iter()
+        .map(|x| x.to_sql("graph", Person::LABEL).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        constraints,
+        [
+            "ALTER TABLE \"graph\".\"Person\" ADD CONSTRAINT \"Person_name_required\" \
+             CHECK(agtype_access_operator(properties, '\"name\"') IS NOT NULL)",
+            "ALTER TABLE \"graph\".\"Person\" ADD CONSTRAINT \"Person_email_required\" \
+             CHECK(agtype_access_operator(properties, '\"email\"') IS NOT NULL)",
+        ]
+    );
+
+    let indexes = Person::indexes()
+        .iter()
+        .map(|x| x.to_sql("graph", Person::LABEL).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        indexes,
+        [
+            "CREATE UNIQUE INDEX \"Person_email_unique\" ON \"graph\".\"Person\" \
+             USING btree (agtype_access_operator(properties, '\"email\"'))",
+            "CREATE UNIQUE INDEX \"Person_nickName_unique\" ON \"graph\".\"Person\" \
+             USING btree (agtype_access_operator(properties, '\"nickName\"'))",
+        ]
+    );
+}
+
+#[test]
+#[ignore]
+fn ensure_schema() {
+    let mut client = Client::connect_age(
+        "host=localhost user=postgres password=passwd port=8081",
+        NoTls,
+    )
+    .unwrap();
+    let graph = "age_test_".to_string()
+        + &rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(7)
+            .map(char::from)
+            .collect::<String>();
+    client.create_graph(&graph).unwrap();
+
+    Person::ensure_schema(&mut client, &graph).unwrap();
+    Knows::ensure_schema(&mut client, &graph).unwrap();
+    // Schema which already exists is kept
+    Person::ensure_schema(&mut client, &graph).unwrap();
+
+    let constraints = client.list_constraints(&graph, Person::LABEL).unwrap();
+    assert_eq!(constraints.len(), 2);
+    let indexes = client.list_indexes(&graph, Person::LABEL).unwrap();
+    assert!(indexes
+        .iter()
+        .any(|x| x.name == "Person_nickName_unique" && x.unique));
+
+    let create = "CREATE (:Person {name: 'John', email: 'john@example.com'})";
+    client.execute_cypher::<()>(&graph, create, None).unwrap();
+    assert!(client.execute_cypher::<()>(&graph, create, None).is_err());
+    assert!(client
+        .execute_cypher::<()>(&graph, "CREATE (:Person {name: 'Jane'})", None)
+        .is_err());
+
+    client.drop_graph(&graph);
+}
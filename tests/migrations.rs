@@ -0,0 +1,97 @@
+#![cfg(feature = "sync")]
+
+use apache_age::sync::{Migration, MigrationStep, Migrations};
+use apache_age::{Error, MigrationDirection};
+use std::fs;
+
+fn migrations() -> Migrations {
+    Migrations::new()
+        .migration(
+            Migration::cypher(1, "people", "CREATE (:Person {name: 'John'})")
+                .down(MigrationStep::cypher("MATCH (n: Person) DELETE n")),
+        )
+        .migration(Migration::new(
+            2,
+            "person_name",
+            MigrationStep::rust(|_, _| Ok(())),
+        ))
+        .migration(Migration::sql(3, "analyze", "SELECT 1").down(MigrationStep::sql("SELECT 1")))
+}
+
+fn versions(plan: &[(MigrationDirection, &Migration)]) -> Vec<(MigrationDirection, i64)> {
+    plan.iter().map(|(d, x)| (*d, x.version())).collect()
+}
+
+#[test]
+fn plan_up_and_down() {
+    use MigrationDirection::*;
+    let migrations = migrations();
+    assert_eq!(migrations.latest_version(), Some(3));
+
+    let plan = migrations.plan(&[], None).unwrap();
+    assert_eq!(versions(&plan), [(Up, 1), (Up, 2), (Up, 3)]);
+
+    let plan = migrations.plan(&[1], Some(2)).unwrap();
+    assert_eq!(versions(&plan), [(Up, 2)]);
+
+    let plan = migrations.plan(&[1, 2, 3], Some(2)).unwrap();
+    assert_eq!(versions(&plan), [(Down, 3)]);
+
+    let plan = migrations.plan(&[1, 2, 3], None).unwrap();
+    assert!(plan.is_empty());
+
+    // Migration 2 has no down step
+    assert!(matches!(
+        migrations.plan(&[1, 2, 3], Some(0)),
+        Err(Error::Migration(_))
+    ));
+    assert!(matches!(
+        migrations.plan(&[1, 4], None),
+        Err(Error::Migration(_))
+    ));
+}
+
+#[test]
+fn duplicated_versions() {
+    let migrations = migrations().migration(Migration::sql(2, "again", "SELECT 1"));
+    assert!(matches!(
+        migrations.plan(&[], None),
+        Err(Error::Migration(_))
+    ));
+}
+
+#[test]
+fn from_dir() {
+    let dir = std::env::temp_dir().join(format!("age_migrations_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("0002_index.up.sql"), "SELECT 2").unwrap();
+    fs::write(dir.join("0001_people.up.cypher"), "CREATE (:Person)").unwrap();
+    fs::write(dir.join("0001_people.down.cypher"), "MATCH (n) DELETE n").unwrap();
+    fs::write(dir.join("README.md"), "skipped").unwrap();
+
+    let migrations = Migrations::from_dir(&dir).unwrap();
+    let loaded: Vec<(i64, &str, bool)> = migrations
+        .iter()
+        .map(|x| (x.version(), x.name(), x.down_step().is_some()))
+        .collect();
+    assert_eq!(loaded, [(1, "people", true), (2, "index", false)]);
+    assert!(matches!(
+        migrations.iter().next().unwrap().up_step(),
+        MigrationStep::Cypher(x) if x == "CREATE (:Person)"
+    ));
+
+    fs::write(dir.join("0003_orphan.down.sql"), "SELECT 3").unwrap();
+    assert!(matches!(
+        Migrations::from_dir(&dir),
+        Err(Error::Migration(_))
+    ));
+    fs::remove_file(dir.join("0003_orphan.down.sql")).unwrap();
+
+    fs::write(dir.join("people.up.sql"), "SELECT 4").unwrap();
+    assert!(matches!(
+        Migrations::from_dir(&dir),
+        Err(Error::Migration(_))
+    ));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,92 @@
+use apache_age::{AgType, Edge, Path, Vertex};
+use postgres_types::{FromSql, Kind, Type};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct Person {
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct Knows {
+    pub since: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct PersonWithFriends {
+    pub person: Vertex<Person>,
+    pub friends: i64,
+    pub knows: Option<Edge<Knows>>,
+}
+
+fn agtype() -> Type {
+    Type::new("agtype".into(), 0, Kind::Simple, "ag_catalog".into())
+}
+
+fn raw(text: &str) -> Vec<u8> {
+    [&[1u8], text.as_bytes()].concat()
+}
+
+const JOHN: &str =
+    r#"{"id": 844424930131969, "label": "Person", "properties": {"name": "John"}}::vertex"#;
+const JANE: &str =
+    r#"{"id": 844424930131970, "label": "Person", "properties": {"name": "Jane"}}::vertex"#;
+const KNOWS: &str = r#"{"id": 1125899906842625, "label": "Knows", "end_id": 844424930131970, "start_id": 844424930131969, "properties": {"since": 2001}}::edge"#;
+
+#[test]
+fn collected_vertices() {
+    let text = format!("[{}, {}]", JOHN, JANE);
+    let people = AgType::<Vec<Vertex<Person>>>::from_sql(&agtype(), &raw(&text)).unwrap();
+
+    assert_eq!(people.0.len(), 2);
+    assert_eq!(people.0[0].properties().name, "John");
+    assert_eq!(u64::from(people.0[1].id()), 844424930131970);
+}
+
+#[test]
+fn vertex_within_map() {
+    let text = format!(
+        r#"{{"knows": {}, "person": {}, "friends": 1}}"#,
+        KNOWS, JOHN
+    );
+    let x = AgType::<PersonWithFriends>::from_sql(&agtype(), &raw(&text)).unwrap();
+
+    assert_eq!(x.0.person.label(), "Person");
+    assert_eq!(x.0.friends, 1);
+    assert_eq!(x.0.knows.unwrap().properties().since, 2001);
+
+    let text = format!(r#"{{"knows": null, "person": {}, "friends": 0}}"#, JOHN);
+    let x = AgType::<PersonWithFriends>::from_sql(&agtype(), &raw(&text)).unwrap();
+    assert!(x.0.knows.is_none());
+}
+
+#[test]
+fn paths_within_list() {
+    let text = format!("[[{}, {}, {}]::path, [{}]::path]", JOHN, KNOWS, JANE, JANE);
+    let paths = AgType::<Vec<Path<Person, Knows>>>::from_sql(&agtype(), &raw(&text)).unwrap();
+
+    assert_eq!(paths.0[0].vertices().len(), 2);
+    assert_eq!(
+        paths.0[0].edges()[0].start_id(),
+        paths.0[0].vertices()[0].id()
+    );
+    assert_eq!(paths.0[1].vertices()[0].properties().name, "Jane");
+    assert!(paths.0[1].edges().is_empty());
+}
+
+#[test]
+fn mismatched_entities() {
+    let text = format!("[{}]", KNOWS);
+    assert!(AgType::<Vec<Vertex<Person>>>::from_sql(&agtype(), &raw(&text)).is_err());
+
+    let text = format!("[{}]", JOHN);
+    assert!(AgType::<Vec<Edge<Knows>>>::from_sql(&agtype(), &raw(&text)).is_err());
+
+    // Any properties fit, so only the edge fields tell the entities apart
+    let text = format!("[{}]", KNOWS);
+    assert!(AgType::<Vec<Vertex<serde_json::Value>>>::from_sql(&agtype(), &raw(&text)).is_err());
+    let text = format!("[{}, {}]", JOHN, JANE);
+    let people =
+        AgType::<Vec<Vertex<serde_json::Value>>>::from_sql(&agtype(), &raw(&text)).unwrap();
+    assert_eq!(people.0[1].properties()["name"], "Jane");
+}
@@ -0,0 +1,102 @@
+use apache_age::{AgType, AgValue, Edge, Vertex};
+use postgres_types::{FromSql, Kind, Type};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct Account {
+    pub balance: f64,
+    pub ratio: f64,
+    pub exact: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Transfer {
+    pub amount: f64,
+    pub count: i64,
+}
+
+fn agtype() -> Type {
+    Type::new("agtype".into(), 0, Kind::Simple, "ag_catalog".into())
+}
+
+fn raw(text: &str) -> Vec<u8> {
+    [&[1u8], text.as_bytes()].concat()
+}
+
+#[test]
+fn special_floats() {
+    let x =
+        AgType::<Vec<f64>>::from_sql(&agtype(), &raw("[NaN, Infinity, -Infinity, 1.5]")).unwrap();
+    assert!(x.0[0].is_nan());
+    assert_eq!(x.0[1], f64::INFINITY);
+    assert_eq!(x.0[2], f64::NEG_INFINITY);
+    assert_eq!(x.0[3], 1.5);
+
+    let value: AgValue = "[NaN, -Infinity::float]".parse().unwrap();
+    assert_eq!(value.to_string(), "[NaN, -Infinity]");
+}
+
+#[test]
+fn numerics() {
+    let x = AgType::<f64>::from_sql(&agtype(), &raw("1.5::numeric")).unwrap();
+    assert_eq!(x.0, 1.5);
+
+    let x = AgType::<u64>::from_sql(&agtype(), &raw("18446744073709551615::numeric")).unwrap();
+    assert_eq!(x.0, u64::MAX);
+
+    let x =
+        AgType::<String>::from_sql(&agtype(), &raw("0.1000000000000000000001::numeric")).unwrap();
+    assert_eq!(x.0, "0.1000000000000000000001");
+
+    assert!(AgType::<i64>::from_sql(&agtype(), &raw("1.5::numeric")).is_err());
+}
+
+#[test]
+fn entity_properties() {
+    let vertex = Vertex::<Account>::from_sql(
+        &agtype(),
+        &raw(r#"{"id": 1, "label": "Account", "properties": {"ratio": NaN, "exact": 10.25::numeric, "balance": 99.99::numeric}}::vertex"#),
+    )
+    .unwrap();
+    assert_eq!(vertex.properties().balance, 99.99);
+    assert!(vertex.properties().ratio.is_nan());
+    assert_eq!(vertex.properties().exact, "10.25");
+
+    let edge = Edge::<Transfer>::from_sql(
+        &agtype(),
+        &raw(r#"{"id": 3, "label": "Transfer", "end_id": 2, "start_id": 1, "properties": {"count": 7::numeric, "amount": -Infinity}}::edge"#),
+    )
+    .unwrap();
+    assert_eq!(edge.properties().amount, f64::NEG_INFINITY);
+    assert_eq!(edge.properties().count, 7);
+}
+
+#[cfg(feature = "bigdecimal")]
+#[test]
+fn bigdecimal() {
+    use bigdecimal::BigDecimal;
+    use std::str::FromStr;
+
+    #[derive(Debug, Deserialize)]
+    struct Ledger {
+        pub total: BigDecimal,
+    }
+
+    let x = AgType::<Ledger>::from_sql(
+        &agtype(),
+        &raw(r#"{"total": 123456789012345678901234567890.000000001::numeric}"#),
+    )
+    .unwrap();
+    assert_eq!(
+        x.0.total,
+        BigDecimal::from_str("123456789012345678901234567890.000000001").unwrap()
+    );
+
+    match "0.5::numeric".parse::<AgValue>().unwrap() {
+        AgValue::Numeric(x) => assert_eq!(
+            x.to_bigdecimal().unwrap(),
+            BigDecimal::from_str("0.5").unwrap()
+        ),
+        x => panic!("Expected numeric, got {:?}", x),
+    }
+}
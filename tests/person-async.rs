@@ -1,8 +1,9 @@
 #![cfg(feature = "tokio")]
 #![allow(unused_must_use)]
 
-use apache_age::tokio::{AgeClient, Client, JoinHandle};
-use apache_age::{NoTls, Vertex};
+use apache_age::tokio::{AgeClient, Client, ConnectionHandle};
+use apache_age::{AgType, LabelKind, NoTls, Vertex};
+use futures_util::TryStreamExt;
 use rand::{distributions::Alphanumeric, Rng};
 use serde::{Deserialize, Serialize};
 
@@ -17,7 +18,7 @@ struct Person {
 struct TestConnection {
     pub client: Client,
     pub graph_name: String,
-    join_handle: JoinHandle<()>,
+    join_handle: ConnectionHandle,
 }
 
 impl TestConnection {
@@ -38,7 +39,7 @@ impl Drop for TestConnection {
     }
 }
 
-async fn connect() -> (Client, JoinHandle<()>, String) {
+async fn connect() -> (Client, ConnectionHandle, String) {
     let (client, join_handle) = Client::connect_age(CONN, NoTls).await.unwrap();
 
     let graph_name = "age_test_".to_string()
@@ -107,3 +108,211 @@ async fn simple_query() {
 
     tc.client.drop_graph(&tc.graph_name).await;
 }
+
+#[tokio::test]
+async fn multiple_columns() {
+    let tc = TestConnection::new().await;
+
+    tc.client
+        .execute_cypher::<()>(
+            &tc.graph_name,
+            "CREATE(n: Person {name: 'John', surname: 'Doe'})",
+            None,
+        )
+        .await;
+
+    let rows = tc
+        .client
+        .query_cypher_columns::<()>(
+            &tc.graph_name,
+            "MATCH (n: Person) RETURN n, count(*) AS total",
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    let person: Vertex<Person> = rows[0].get("n");
+    let total: AgType<i64> = rows[0].get("total");
+    assert_eq!(person.properties().name, "John");
+    assert_eq!(total.0, 1);
+
+    tc.client.drop_graph(&tc.graph_name).await;
+}
+
+#[tokio::test]
+async fn stream_rows() {
+    let tc = TestConnection::new().await;
+
+    tc.client
+        .execute_cypher::<()>(
+            &tc.graph_name,
+            "UNWIND range(1, 5) AS i CREATE (n: Person {name: toString(i), surname: 'Doe'})",
+            None,
+        )
+        .await;
+
+    let mut people = tc
+        .client
+        .query_cypher_stream::<Vertex<Person>, ()>(
+            &tc.graph_name,
+            "MATCH (n: Person) RETURN n ORDER BY n.name",
+            None,
+        )
+        .await
+        .unwrap();
+
+    let mut names = vec![];
+    while let Some(person) = people.try_next().await.unwrap() {
+        names.push(person.properties().name.clone());
+    }
+    assert_eq!(names, ["1", "2", "3", "4", "5"]);
+
+    tc.client.drop_graph(&tc.graph_name).await;
+}
+
+#[tokio::test]
+async fn graph_transactions() {
+    let mut tc = TestConnection::new().await;
+    let graph = tc.graph_name.clone();
+
+    let result = tc
+        .client
+        .with_graph_transaction(|tx| {
+            let graph = graph.clone();
+            Box::pin(async move {
+                tx.execute_cypher::<()>(&graph, "CREATE (n: Person {name: 'John'})", None)
+                    .await?;
+
+                let nested = tx
+                    .with_graph_transaction(|savepoint| {
+                        Box::pin(async move {
+                            savepoint
+                                .execute_cypher::<()>(
+                                    &graph,
+                                    "CREATE (n: Person {name: 'Jane'})",
+                                    None,
+                                )
+                                .await?;
+                            savepoint
+                                .execute_cypher::<()>(&graph, "CREATE (n: Person", None)
+                                .await
+                        })
+                    })
+                    .await;
+                assert!(nested.is_err());
+
+                Ok(())
+            })
+        })
+        .await;
+    assert!(result.is_ok());
+
+    let names: Vec<AgType<String>> = tc
+        .client
+        .query_cypher_as::<_, ()>(&tc.graph_name, "MATCH (n: Person) RETURN n.name", None)
+        .await
+        .unwrap();
+    assert_eq!(names.len(), 1);
+    assert_eq!(names[0].0, "John");
+
+    tc.client.drop_graph(&tc.graph_name).await;
+}
+
+#[tokio::test]
+async fn labels() {
+    let tc = TestConnection::new().await;
+
+    tc.client
+        .create_vlabel(&tc.graph_name, "Person")
+        .await
+        .unwrap();
+    tc.client
+        .create_elabel(&tc.graph_name, "KNOWS")
+        .await
+        .unwrap();
+    assert!(tc
+        .client
+        .label_exists(&tc.graph_name, "Person")
+        .await
+        .unwrap());
+
+    let labels = tc.client.list_labels(&tc.graph_name).await.unwrap();
+    let kinds: Vec<LabelKind> = labels.iter().map(|x| x.kind).collect();
+    assert_eq!(kinds, [LabelKind::Vertex, LabelKind::Edge]);
+
+    tc.client.drop_label(&tc.graph_name, "KNOWS").await.unwrap();
+    assert_eq!(
+        tc.client.list_labels(&tc.graph_name).await.unwrap().len(),
+        1
+    );
+
+    tc.client.drop_graph(&tc.graph_name).await;
+}
+
+#[tokio::test]
+async fn migrations() {
+    use apache_age::tokio::{Migration, MigrationStep, Migrations};
+
+    let mut tc = TestConnection::new().await;
+    let migrations = Migrations::new()
+        .migration(Migration::cypher(
+            1,
+            "people",
+            "CREATE (:Person {name: 'John'})",
+        ))
+        .migration(Migration::new(
+            2,
+            "person_name",
+            MigrationStep::rust(|tx, graph| {
+                Box::pin(async move {
+                    tx.unique_index(graph, "Person", "person_name_idx", "name")
+                        .await?;
+                    Ok(())
+                })
+            }),
+        ));
+
+    let graph = tc.graph_name.clone();
+    assert_eq!(
+        tc.client.migrate(&graph, &migrations).await.unwrap(),
+        [1, 2]
+    );
+    let applied = tc.client.applied_migrations(&graph).await.unwrap();
+    assert_eq!(applied.len(), 2);
+
+    let result = tc
+        .client
+        .execute_cypher::<()>(&graph, "CREATE (:Person {name: 'John'})", None)
+        .await;
+    assert!(result.is_err());
+
+    tc.client.drop_graph(&graph).await;
+}
+
+#[tokio::test]
+async fn create_vertices() {
+    let tc = TestConnection::new().await;
+
+    let people: Vec<Person> = (0..5)
+        .map(|i| Person {
+            name: format!("John {}", i),
+            surname: "Doe".to_string(),
+        })
+        .collect();
+    let created = tc
+        .client
+        .create_vertices(&tc.graph_name, "Person", people.clone(), Some(2))
+        .await
+        .unwrap();
+    assert_eq!(created, 5);
+
+    let vertices = tc
+        .client
+        .create_vertices_returning(&tc.graph_name, "Person", people, None)
+        .await
+        .unwrap();
+    assert_eq!(vertices.len(), 5);
+    assert_eq!(vertices[4].properties().name, "John 4");
+
+    tc.client.drop_graph(&tc.graph_name).await;
+}
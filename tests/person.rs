@@ -2,7 +2,10 @@
 #![allow(unused_must_use)]
 
 use apache_age::sync::{AgeClient, Client};
-use apache_age::{AgType, NoTls, Vertex};
+use apache_age::{
+    params, AgType, AgValue, Constraint, CountMethod, GraphId, Index, LabelInfo, LabelKind, NoTls,
+    Vertex,
+};
 use rand::{distributions::Alphanumeric, Rng};
 use serde::{Deserialize, Serialize};
 
@@ -139,6 +142,126 @@ fn person() {
     assert_eq!(x.0.surname, "Bohr");
 }
 
+#[test]
+fn dynamic_value() {
+    let mut tc = TestConnection::new();
+
+    tc.client.execute_cypher::<()>(
+        &tc.graph_name,
+        "CREATE(n: Person {name: 'John', surname: 'Doe'})",
+        None,
+    );
+
+    let rows = tc
+        .client
+        .query_cypher::<()>(
+            &tc.graph_name,
+            "MATCH (n: Person) RETURN {people: collect(n), count: count(n)}",
+            None,
+        )
+        .unwrap();
+
+    let value: AgValue = rows[0].get(0);
+    let map = value.as_map().unwrap();
+    assert_eq!(map["count"].as_i64(), Some(1));
+
+    let person = map["people"].as_list().unwrap()[0].as_vertex().unwrap();
+    assert_eq!(person.label(), "Person");
+    assert_eq!(person.properties()["surname"].as_str(), Some("Doe"));
+}
+
+#[derive(Debug, Deserialize)]
+struct PersonWithCount {
+    pub person: Vertex<Person>,
+    pub count: i64,
+}
+
+#[test]
+fn nested_vertices() {
+    let mut tc = TestConnection::new();
+
+    tc.client.execute_cypher::<()>(
+        &tc.graph_name,
+        "CREATE(n: Person {name: 'John', surname: 'Doe'})",
+        None,
+    );
+
+    let rows = tc
+        .client
+        .query_cypher::<()>(&tc.graph_name, "MATCH (n: Person) RETURN collect(n)", None)
+        .unwrap();
+    let people: AgType<Vec<Vertex<Person>>> = rows[0].get(0);
+    assert_eq!(people.0[0].properties().name, "John");
+
+    let rows = tc
+        .client
+        .query_cypher::<()>(
+            &tc.graph_name,
+            "MATCH (n: Person) RETURN {person: n, count: count(n)}",
+            None,
+        )
+        .unwrap();
+    let x: AgType<PersonWithCount> = rows[0].get(0);
+    assert_eq!(x.0.person.properties().surname, "Doe");
+    assert_eq!(x.0.count, 1);
+}
+
+#[test]
+fn graph_id() {
+    let mut tc = TestConnection::new();
+
+    let rows = tc
+        .client
+        .query_cypher::<()>(
+            &tc.graph_name,
+            "CREATE(n: Person {name: 'John', surname: 'Doe'}) RETURN n",
+            None,
+        )
+        .unwrap();
+    let person: Vertex<Person> = rows[0].get(0);
+
+    let rows = tc
+        .client
+        .query(
+            &format!(
+                "SELECT id FROM \"{}\".\"Person\" WHERE id = $1",
+                tc.graph_name
+            ),
+            &[&person.id()],
+        )
+        .unwrap();
+    let id: GraphId = rows[0].get(0);
+    assert_eq!(id, person.id());
+    assert_eq!(id.entry_id(), 1);
+}
+
+#[test]
+fn vertex_round_trip() {
+    let mut tc = TestConnection::new();
+
+    let rows = tc
+        .client
+        .query_cypher::<()>(
+            &tc.graph_name,
+            "CREATE(n: Person {name: 'John', surname: 'Doe'}) RETURN n",
+            None,
+        )
+        .unwrap();
+    let person: Vertex<Person> = rows[0].get(0);
+
+    tc.client
+        .simple_query("CREATE TEMPORARY TABLE audit (entry agtype)")
+        .unwrap();
+    tc.client
+        .execute("INSERT INTO audit (entry) VALUES ($1)", &[&person])
+        .unwrap();
+
+    let rows = tc.client.query("SELECT entry FROM audit", &[]).unwrap();
+    let stored: Vertex<Person> = rows[0].get(0);
+    assert_eq!(stored.id(), person.id());
+    assert_eq!(stored.properties().name, "John");
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 struct IdPassing {
     id: usize,
@@ -212,3 +335,615 @@ fn required_constraint() {
         .execute_cypher::<()>(&tc.graph_name, "CREATE(n: Person {name: 'Name'})", None)
         .is_err());
 }
+
+#[test]
+fn multiple_columns() {
+    let mut tc = TestConnection::new();
+
+    tc.client.execute_cypher::<()>(
+        &tc.graph_name,
+        "CREATE(n: Person {name: 'John', surname: 'Doe'})",
+        None,
+    );
+
+    let rows = tc
+        .client
+        .query_cypher_columns::<()>(
+            &tc.graph_name,
+            "MATCH (n: Person) RETURN n, n.name, count(*) AS total",
+            None,
+            None,
+        )
+        .unwrap();
+    let person: Vertex<Person> = rows[0].get("n");
+    let name: AgType<String> = rows[0].get("n.name");
+    let total: AgType<i64> = rows[0].get("total");
+    assert_eq!(person.properties().surname, "Doe");
+    assert_eq!(name.0, "John");
+    assert_eq!(total.0, 1);
+
+    let rows = tc
+        .client
+        .query_cypher_columns::<()>(
+            &tc.graph_name,
+            "MATCH (n: Person) RETURN n.name, n.surname",
+            Some(&["name", "surname"]),
+            None,
+        )
+        .unwrap();
+    let surname: AgType<String> = rows[0].get("surname");
+    assert_eq!(surname.0, "Doe");
+}
+
+#[test]
+fn structured_errors() {
+    let mut tc = TestConnection::new();
+
+    match tc
+        .client
+        .execute_cypher::<()>("missing_graph", "CREATE (n: Person)", None)
+    {
+        Err(apache_age::Error::MissingGraph(name)) => assert_eq!(name, "missing_graph"),
+        x => panic!("Expected missing graph, got {:?}", x),
+    }
+
+    match tc
+        .client
+        .execute_cypher::<()>(&tc.graph_name, "CREATE (n: Person", None)
+    {
+        Err(apache_age::Error::Database { sqlstate, .. }) => {
+            assert_eq!(sqlstate, postgres::error::SqlState::SYNTAX_ERROR)
+        }
+        x => panic!("Expected database error, got {:?}", x),
+    }
+
+    tc.client
+        .execute_cypher::<()>(&tc.graph_name, "CREATE (n: Person {name: 1})", None);
+    match tc.client.query_one_cypher_as::<Vertex<Person>, ()>(
+        &tc.graph_name,
+        "MATCH (n: Person) RETURN n",
+        None,
+    ) {
+        Err(apache_age::Error::Decode { expected, .. }) => {
+            assert!(expected.unwrap().contains("Vertex"))
+        }
+        x => panic!("Expected decode error, got {:?}", x),
+    }
+}
+
+#[test]
+fn iterate_in_batches() {
+    let mut tc = TestConnection::new();
+
+    tc.client.execute_cypher::<()>(
+        &tc.graph_name,
+        "UNWIND range(1, 5) AS i CREATE (n: Person {name: toString(i), surname: 'Doe'})",
+        None,
+    );
+
+    let people = tc
+        .client
+        .query_cypher_iter::<Vertex<Person>, ()>(
+            &tc.graph_name,
+            "MATCH (n: Person) RETURN n ORDER BY n.name",
+            None,
+            Some(2),
+        )
+        .unwrap()
+        .map(|x| x.unwrap().properties().name.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(people, ["1", "2", "3", "4", "5"]);
+
+    // Connection is usable once the iterator is dropped
+    let mut people = tc
+        .client
+        .query_cypher_iter::<(AgType<String>, AgType<String>), ()>(
+            &tc.graph_name,
+            "MATCH (n: Person) RETURN n.name, n.surname",
+            None,
+            None,
+        )
+        .unwrap();
+    assert_eq!(people.next().unwrap().unwrap().1 .0, "Doe");
+    drop(people);
+
+    assert!(tc.client.graph_exists(&tc.graph_name).unwrap());
+}
+
+#[test]
+fn named_params() {
+    let mut tc = TestConnection::new();
+
+    tc.client
+        .execute_cypher_params(
+            &tc.graph_name,
+            "CREATE(n: Person {name: $name, surname: $surname})",
+            &params! { name: "John", surname: "Doe" },
+        )
+        .unwrap();
+
+    let people: Vec<Vertex<Person>> = tc
+        .client
+        .query_cypher_params(
+            &tc.graph_name,
+            "MATCH (n: Person {name: $name}) RETURN n",
+            &params! { name: "John" },
+        )
+        .unwrap();
+    assert_eq!(people.len(), 1);
+    assert_eq!(people[0].properties().surname, "Doe");
+
+    let result = tc.client.execute_cypher_params(
+        &tc.graph_name,
+        "CREATE(n: Person {name: $name, surname: $surname})",
+        &params! { name: "John", surnmae: "Doe" },
+    );
+    assert!(matches!(
+        result,
+        Err(apache_age::Error::InvalidParameters { .. })
+    ));
+}
+
+#[test]
+fn injection_safe_names() {
+    let mut tc = TestConnection::new();
+
+    let result = tc.client.execute_cypher::<()>(
+        "graph', $$ RETURN 1 $$) as (v agtype); DROP TABLE users; --",
+        "CREATE (n: Person)",
+        None,
+    );
+    assert!(matches!(
+        result,
+        Err(apache_age::Error::InvalidIdentifier(_))
+    ));
+
+    let result = tc
+        .client
+        .unique_index(&tc.graph_name, "Person\" CASCADE", "idx", "name");
+    assert!(matches!(
+        result,
+        Err(apache_age::Error::InvalidIdentifier(_))
+    ));
+
+    tc.client
+        .execute_cypher::<()>(
+            &tc.graph_name,
+            "CREATE (n: Person {name: 'costs $$5', surname: 'O''Neil'})",
+            None,
+        )
+        .unwrap();
+    tc.client
+        .unique_index(&tc.graph_name, "Person", "person \"name\" idx", "name'")
+        .unwrap();
+
+    let person: Vertex<Person> = tc
+        .client
+        .query_one_cypher_as::<_, ()>(&tc.graph_name, "MATCH (n: Person) RETURN n", None)
+        .unwrap();
+    assert_eq!(person.properties().name, "costs $$5");
+}
+
+#[test]
+fn graph_transactions() {
+    let mut tc = TestConnection::new();
+    let graph = tc.graph_name.clone();
+
+    let result = tc.client.with_graph_transaction(|tx| {
+        tx.execute_cypher::<()>(
+            &graph,
+            "CREATE (n: Person {name: 'John', surname: 'Doe'})",
+            None,
+        )?;
+        tx.execute_cypher::<()>(&graph, "CREATE (n: Person", None)
+    });
+    assert!(matches!(result, Err(apache_age::Error::Database { .. })));
+
+    tc.client
+        .with_graph_transaction(|tx| {
+            tx.execute_cypher::<()>(
+                &graph,
+                "CREATE (n: Person {name: 'Jane', surname: 'Doe'})",
+                None,
+            )?;
+
+            let nested = tx.with_graph_transaction(|savepoint| {
+                savepoint.execute_cypher::<()>(
+                    &graph,
+                    "CREATE (n: Person {name: 'Jim', surname: 'Doe'})",
+                    None,
+                )?;
+                savepoint.execute_cypher::<()>(&graph, "CREATE (n: Person", None)
+            });
+            assert!(nested.is_err());
+
+            tx.with_graph_transaction(|savepoint| {
+                savepoint.unique_index(&graph, "Person", "person_name_idx", "name")
+            })
+        })
+        .unwrap();
+
+    let people: Vec<Vertex<Person>> = tc
+        .client
+        .query_cypher_as::<_, ()>(&tc.graph_name, "MATCH (n: Person) RETURN n", None)
+        .unwrap();
+    assert_eq!(people.len(), 1);
+    assert_eq!(people[0].properties().name, "Jane");
+}
+
+#[test]
+fn session_config() {
+    let config =
+        apache_age::AgeConfig::new("host=localhost user=postgres password=passwd port=8081")
+            .search_path(apache_age::SearchPath::Prepend)
+            .statement_timeout(std::time::Duration::from_secs(3))
+            .application_name("age_session_test");
+    let mut client = Client::connect_age(config, NoTls).unwrap();
+
+    let timeout: String = client
+        .query_one("SHOW statement_timeout", &[])
+        .unwrap()
+        .get(0);
+    let search_path: String = client.query_one("SHOW search_path", &[]).unwrap().get(0);
+    let application: String = client
+        .query_one("SHOW application_name", &[])
+        .unwrap()
+        .get(0);
+    assert_eq!(timeout, "3s");
+    assert!(search_path.starts_with("ag_catalog, "));
+    assert_eq!(application, "age_session_test");
+    assert!(!client.graph_exists("age_session_missing").unwrap());
+}
+
+#[test]
+fn labels() {
+    let mut tc = TestConnection::new();
+
+    assert!(!tc.client.label_exists(&tc.graph_name, "Person").unwrap());
+    tc.client.create_vlabel(&tc.graph_name, "Person").unwrap();
+    tc.client.create_elabel(&tc.graph_name, "KNOWS").unwrap();
+    assert!(tc.client.label_exists(&tc.graph_name, "Person").unwrap());
+
+    let labels = tc.client.list_labels(&tc.graph_name).unwrap();
+    assert_eq!(
+        labels,
+        vec![
+            LabelInfo {
+                name: "Person".into(),
+                kind: LabelKind::Vertex
+            },
+            LabelInfo {
+                name: "KNOWS".into(),
+                kind: LabelKind::Edge
+            },
+        ]
+    );
+
+    // Index can be created before any vertex exists
+    tc.client
+        .unique_index(&tc.graph_name, "Person", "person_name_idx", "name")
+        .unwrap();
+
+    tc.client.drop_label(&tc.graph_name, "KNOWS").unwrap();
+    assert!(!tc.client.label_exists(&tc.graph_name, "KNOWS").unwrap());
+
+    let result = tc.client.create_vlabel(&tc.graph_name, "Person\" CASCADE");
+    assert!(matches!(
+        result,
+        Err(apache_age::Error::InvalidIdentifier(_))
+    ));
+}
+
+#[test]
+fn graph_catalog() {
+    let mut tc = TestConnection::new();
+
+    tc.client
+        .execute_cypher::<()>(
+            &tc.graph_name,
+            "CREATE (:Person {name: 'John'})-[:KNOWS]->(:Person {name: 'Jane'})",
+            None,
+        )
+        .unwrap();
+
+    assert!(tc.client.list_graphs().unwrap().contains(&tc.graph_name));
+
+    let info = tc.client.graph_info(&tc.graph_name).unwrap();
+    assert_eq!(info.name, tc.graph_name);
+    assert_eq!(info.namespace, tc.graph_name);
+    let labels: Vec<&str> = info.labels.iter().map(|x| x.name.as_str()).collect();
+    assert_eq!(labels, ["Person", "KNOWS"]);
+
+    let stats = tc
+        .client
+        .graph_stats(&tc.graph_name, CountMethod::Exact)
+        .unwrap();
+    assert_eq!(stats.vertex_count(), 2);
+    assert_eq!(stats.edge_count(), 1);
+
+    tc.client
+        .simple_query(&format!("ANALYZE \"{}\".\"Person\"", tc.graph_name))
+        .unwrap();
+    let estimated = tc
+        .client
+        .graph_stats(&tc.graph_name, CountMethod::Estimate)
+        .unwrap();
+    assert_eq!(estimated.labels.len(), stats.labels.len());
+
+    assert!(matches!(
+        tc.client.graph_info("age_catalog_missing"),
+        Err(apache_age::Error::MissingGraph(_))
+    ));
+    assert!(matches!(
+        tc.client
+            .graph_stats("age_catalog_missing", CountMethod::Estimate),
+        Err(apache_age::Error::MissingGraph(_))
+    ));
+}
+
+#[test]
+fn indexes() {
+    let mut tc = TestConnection::new();
+
+    tc.client
+        .execute_cypher::<()>(
+            &tc.graph_name,
+            "CREATE (:Person {name: 'John', surname: 'Doe'})-[:KNOWS]->(:Person {name: 'Jane', surname: 'Doe'})",
+            None,
+        )
+        .unwrap();
+
+    let person_name = Index::new("person_name_idx")
+        .property("surname")
+        .property("name");
+    tc.client
+        .create_index(&tc.graph_name, "Person", &person_name)
+        .unwrap();
+    tc.client
+        .create_index(&tc.graph_name, "Person", &Index::gin("person_props_idx"))
+        .unwrap();
+    tc.client
+        .create_index(
+            &tc.graph_name,
+            "KNOWS",
+            &Index::new("knows_start_idx").start_id(),
+        )
+        .unwrap();
+
+    let indexes = tc.client.list_indexes(&tc.graph_name, "Person").unwrap();
+    let name = indexes
+        .iter()
+        .find(|x| x.name == "person_name_idx")
+        .unwrap();
+    assert_eq!(name.method, "btree");
+    assert!(!name.unique);
+    let props = indexes
+        .iter()
+        .find(|x| x.name == "person_props_idx")
+        .unwrap();
+    assert_eq!(props.method, "gin");
+
+    // Not unique, so the same names are allowed
+    tc.client
+        .execute_cypher::<()>(
+            &tc.graph_name,
+            "CREATE (:Person {name: 'John', surname: 'Doe'})",
+            None,
+        )
+        .unwrap();
+
+    tc.client
+        .drop_index(&tc.graph_name, "person_name_idx")
+        .unwrap();
+    let indexes = tc.client.list_indexes(&tc.graph_name, "Person").unwrap();
+    assert!(indexes.iter().all(|x| x.name != "person_name_idx"));
+    assert!(tc
+        .client
+        .list_indexes(&tc.graph_name, "KNOWS")
+        .unwrap()
+        .iter()
+        .any(|x| x.name == "knows_start_idx"));
+}
+
+#[test]
+fn typed_constraints() {
+    let mut tc = TestConnection::new();
+    tc.client.create_vlabel(&tc.graph_name, "Person").unwrap();
+
+    let constraint = Constraint::new("person_check")
+        .required("name")
+        .length("name", 1..=16)
+        .range("age", 0.0..=150.0)
+        .one_of("status", ["active", "banned"]);
+    tc.client
+        .create_constraint(&tc.graph_name, "Person", &constraint)
+        .unwrap();
+
+    let constraints = tc
+        .client
+        .list_constraints(&tc.graph_name, "Person")
+        .unwrap();
+    assert_eq!(constraints.len(), 1);
+    assert_eq!(constraints[0].name, "person_check");
+    assert!(constraints[0].definition.starts_with("CHECK"));
+
+    for (cypher, valid) in [
+        (
+            "CREATE (n: Person {name: 'John', age: 42, status: 'active'})",
+            true,
+        ),
+        ("CREATE (n: Person {name: 'Jane'})", true),
+        ("CREATE (n: Person {age: 42})", false),
+        ("CREATE (n: Person {name: ''})", false),
+        ("CREATE (n: Person {name: 42})", false),
+        ("CREATE (n: Person {name: 'John', age: 151})", false),
+        ("CREATE (n: Person {name: 'John', age: '42'})", false),
+        (
+            "CREATE (n: Person {name: 'John', status: 'deleted'})",
+            false,
+        ),
+    ] {
+        let result = tc.client.execute_cypher::<()>(&tc.graph_name, cypher, None);
+        assert_eq!(result.is_ok(), valid, "{}", cypher);
+    }
+
+    tc.client
+        .drop_constraint(&tc.graph_name, "Person", "person_check")
+        .unwrap();
+    tc.client
+        .execute_cypher::<()>(&tc.graph_name, "CREATE (n: Person {age: 42})", None)
+        .unwrap();
+    assert!(tc
+        .client
+        .list_constraints(&tc.graph_name, "Person")
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn migrations() {
+    use apache_age::sync::{Migration, MigrationStep, Migrations};
+
+    let mut tc = TestConnection::new();
+    let migrations = Migrations::new()
+        .migration(
+            Migration::cypher(
+                1,
+                "people",
+                "CREATE (:Person {name: 'John; Doe'}); CREATE (:Person {name: 'Jane'})",
+            )
+            .down(MigrationStep::cypher("MATCH (n: Person) DELETE n")),
+        )
+        .migration(
+            Migration::new(
+                2,
+                "person_name",
+                MigrationStep::rust(|tx, graph| {
+                    tx.unique_index(graph, "Person", "person_name_idx", "name")?;
+                    Ok(())
+                }),
+            )
+            .down(MigrationStep::rust(|tx, graph| {
+                tx.drop_index(graph, "person_name_idx")?;
+                Ok(())
+            })),
+        );
+
+    assert_eq!(
+        tc.client.migrate(&tc.graph_name, &migrations).unwrap(),
+        [1, 2]
+    );
+    assert!(tc
+        .client
+        .migrate(&tc.graph_name, &migrations)
+        .unwrap()
+        .is_empty());
+
+    let applied = tc.client.applied_migrations(&tc.graph_name).unwrap();
+    let applied: Vec<(i64, &str)> = applied
+        .iter()
+        .map(|x| (x.version, x.name.as_str()))
+        .collect();
+    assert_eq!(applied, [(1, "people"), (2, "person_name")]);
+
+    let people: Vec<AgType<String>> = tc
+        .client
+        .query_cypher_as::<_, ()>(&tc.graph_name, "MATCH (n: Person) RETURN n.name", None)
+        .unwrap();
+    assert_eq!(people.len(), 2);
+
+    // Failed migration leaves nothing behind
+    let failing = Migrations::new()
+        .migration(Migration::cypher(1, "people", "CREATE (:Person)"))
+        .migration(Migration::cypher(2, "person_name", "CREATE (:Person)"))
+        .migration(Migration::sql(
+            3,
+            "broken",
+            "SELECT * FROM age_missing_table",
+        ));
+    assert!(tc.client.migrate(&tc.graph_name, &failing).is_err());
+    assert_eq!(
+        tc.client.applied_migrations(&tc.graph_name).unwrap().len(),
+        2
+    );
+
+    assert_eq!(
+        tc.client
+            .migrate_to(&tc.graph_name, &migrations, 0)
+            .unwrap(),
+        [2, 1]
+    );
+    assert!(tc
+        .client
+        .applied_migrations(&tc.graph_name)
+        .unwrap()
+        .is_empty());
+    assert!(tc
+        .client
+        .list_indexes(&tc.graph_name, "Person")
+        .unwrap()
+        .iter()
+        .all(|x| x.name != "person_name_idx"));
+}
+
+#[test]
+fn create_vertices() {
+    let mut tc = TestConnection::new();
+
+    let people = (0..25).map(|i| Person {
+        name: format!("John {}", i),
+        surname: "Doe".to_string(),
+    });
+    let created = tc
+        .client
+        .create_vertices(&tc.graph_name, "Person", people, Some(10))
+        .unwrap();
+    assert_eq!(created, 25);
+
+    let count: AgType<i64> = tc
+        .client
+        .query_one_cypher_as::<_, ()>(&tc.graph_name, "MATCH (n: Person) RETURN count(n)", None)
+        .unwrap();
+    assert_eq!(count.0, 25);
+
+    let people = vec![Person {
+        name: "Jane".to_string(),
+        surname: "O'Neil".to_string(),
+    }];
+    let vertices = tc
+        .client
+        .create_vertices_returning(&tc.graph_name, "Person", people, None)
+        .unwrap();
+    assert_eq!(vertices.len(), 1);
+    assert_eq!(vertices[0].label(), "Person");
+    assert_eq!(vertices[0].properties().surname, "O'Neil");
+
+    let result = tc
+        .client
+        .create_vertices(&tc.graph_name, "Person", [1, 2, 3], None);
+    assert!(matches!(result, Err(apache_age::Error::Serialization(_))));
+    let result = tc.client.create_vertices(
+        &tc.graph_name,
+        "Person) DETACH DELETE (m",
+        [params! {}],
+        None,
+    );
+    assert!(matches!(
+        result,
+        Err(apache_age::Error::InvalidIdentifier(_))
+    ));
+    let result = tc.client.create_vertices(
+        &tc.graph_name,
+        "Person",
+        [std::collections::HashMap::from([("score", f64::NAN)])],
+        None,
+    );
+    assert!(matches!(result, Err(apache_age::Error::Serialization(_))));
+
+    // Labels are quoted, so keywords can be used
+    let created = tc
+        .client
+        .create_vertices(&tc.graph_name, "Match", [params! { name: "John" }], None)
+        .unwrap();
+    assert_eq!(created, 1);
+}
@@ -0,0 +1,130 @@
+#![cfg(any(feature = "deadpool", feature = "bb8", feature = "r2d2"))]
+
+#[cfg(any(feature = "bb8", feature = "r2d2"))]
+use std::time::Duration;
+
+const CONN: &str = "host=localhost user=postgres password=passwd port=8081";
+#[cfg(any(feature = "bb8", feature = "r2d2"))]
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+#[cfg(feature = "deadpool")]
+#[test]
+fn deadpool_recycling_method() {
+    use apache_age::pool::deadpool::{recycling_method, RecyclingMethod};
+    use apache_age::{AgeConfig, SearchPath};
+
+    let config = AgeConfig::new(CONN);
+    assert_eq!(
+        recycling_method(RecyclingMethod::Fast, &config),
+        RecyclingMethod::Fast
+    );
+
+    let verified = recycling_method(RecyclingMethod::Verified, &config);
+    assert!(verified.query().unwrap().starts_with("SET search_path"));
+
+    let clean = recycling_method(RecyclingMethod::Clean, &config);
+    let query = clean.query().unwrap();
+    assert!(query.starts_with(RecyclingMethod::Clean.query().unwrap().trim_end()));
+    assert!(query.ends_with("SET search_path = ag_catalog, \"$user\", public;"));
+
+    let custom = recycling_method(RecyclingMethod::Custom("RESET ALL;".into()), &config);
+    assert!(custom
+        .query()
+        .unwrap()
+        .starts_with("RESET ALL; SET search_path"));
+
+    let custom = recycling_method(RecyclingMethod::Custom("SELECT 1 ".into()), &config);
+    assert_eq!(
+        custom.query().unwrap(),
+        "SELECT 1; SET search_path = ag_catalog, \"$user\", public;"
+    );
+
+    let keep = AgeConfig::new(CONN).search_path(SearchPath::Keep);
+    assert_eq!(
+        recycling_method(RecyclingMethod::Clean, &keep),
+        RecyclingMethod::Clean
+    );
+}
+
+#[cfg(feature = "deadpool")]
+#[tokio::test]
+async fn deadpool_pool() {
+    use apache_age::pool::deadpool::{pool_builder, ManagerConfig, RecyclingMethod};
+    use apache_age::tokio::AgeClient;
+    use apache_age::NoTls;
+
+    let manager_config = ManagerConfig {
+        recycling_method: RecyclingMethod::Clean,
+    };
+    let pool = pool_builder(CONN, NoTls, manager_config)
+        .unwrap()
+        .max_size(1)
+        .build()
+        .unwrap();
+
+    let client = pool.get().await.unwrap();
+    client.simple_query("RESET ALL").await.unwrap();
+    drop(client);
+
+    let client = pool.get().await.unwrap();
+    let search_path: String = client
+        .query_one("SHOW search_path", &[])
+        .await
+        .unwrap()
+        .get(0);
+    assert!(search_path.starts_with("ag_catalog"));
+    assert!(!client.graph_exists("age_pool_missing").await.unwrap());
+}
+
+#[cfg(feature = "bb8")]
+#[tokio::test]
+async fn bb8_pool() {
+    use apache_age::pool::bb8::AgeConnectionManager;
+    use apache_age::tokio::AgeClient;
+    use apache_age::NoTls;
+
+    let manager = AgeConnectionManager::new(CONN, NoTls).unwrap();
+    let pool = bb8::Pool::builder()
+        .max_size(1)
+        .connection_timeout(TIMEOUT)
+        .build(manager)
+        .await
+        .unwrap();
+
+    let client = pool.get().await.unwrap();
+    client.simple_query("RESET ALL").await.unwrap();
+    drop(client);
+
+    let client = pool.get().await.unwrap();
+    let search_path: String = client
+        .query_one("SHOW search_path", &[])
+        .await
+        .unwrap()
+        .get(0);
+    assert!(search_path.starts_with("ag_catalog"));
+    assert!(!client.graph_exists("age_pool_missing").await.unwrap());
+}
+
+#[cfg(feature = "r2d2")]
+#[test]
+fn r2d2_pool() {
+    use apache_age::pool::r2d2::AgeConnectionManager;
+    use apache_age::sync::AgeClient;
+    use apache_age::NoTls;
+
+    let manager = AgeConnectionManager::new(CONN, NoTls).unwrap();
+    let pool = r2d2::Pool::builder()
+        .max_size(1)
+        .connection_timeout(TIMEOUT)
+        .build(manager)
+        .unwrap();
+
+    let mut client = pool.get().unwrap();
+    client.simple_query("RESET ALL").unwrap();
+    drop(client);
+
+    let mut client = pool.get().unwrap();
+    let search_path: String = client.query_one("SHOW search_path", &[]).unwrap().get(0);
+    assert!(search_path.starts_with("ag_catalog"));
+    assert!(!client.graph_exists("age_pool_missing").unwrap());
+}
@@ -0,0 +1,63 @@
+use apache_age::{return_columns, Error};
+
+#[test]
+fn simple_return() {
+    assert_eq!(return_columns("MATCH (n) RETURN n").unwrap(), ["n"]);
+    assert_eq!(
+        return_columns("MATCH (n:Person) RETURN n.name, n.surname, count(*)").unwrap(),
+        ["n.name", "n.surname", "c3"]
+    );
+    assert_eq!(
+        return_columns("match (n) return distinct n.name as name order by name limit 3").unwrap(),
+        ["name"]
+    );
+}
+
+#[test]
+fn aliases() {
+    assert_eq!(
+        return_columns("MATCH (a)-[e]->(b) RETURN a AS `from`, e, b AS `weird `` name`").unwrap(),
+        ["from", "e", "weird ` name"]
+    );
+    assert_eq!(
+        return_columns("MATCH (n) RETURN {a: n.a, b: n.b} AS x, [1, 2]").unwrap(),
+        ["x", "c2"]
+    );
+    assert_eq!(
+        return_columns("RETURN 1 AS x, 2 AS x").unwrap(),
+        ["x", "x_2"]
+    );
+}
+
+#[test]
+fn nested_and_quoted_keywords() {
+    assert_eq!(
+        return_columns(
+            "MATCH (n {name: 'RETURN a, b'}) WITH n, \"x, y\" AS z \
+             // RETURN comment, c\n\
+             RETURN n.return, [x IN n.list WHERE x > 0 | x] AS list, $order SKIP 1"
+        )
+        .unwrap(),
+        ["n.return", "list", "c3"]
+    );
+    assert_eq!(
+        return_columns("MATCH (n) RETURN n UNION MATCH (m) RETURN m").unwrap(),
+        ["m"]
+    );
+}
+
+#[test]
+fn unknown_columns() {
+    assert_eq!(
+        return_columns("CREATE (n:Person {name: 'a'})").unwrap(),
+        ["v"]
+    );
+    assert!(matches!(
+        return_columns("MATCH (a)-[e]->(b) RETURN *"),
+        Err(Error::Serialization(_))
+    ));
+    assert!(matches!(
+        return_columns("MATCH (n) RETURN DISTINCT * ORDER BY n"),
+        Err(Error::Serialization(_))
+    ));
+}
@@ -0,0 +1,104 @@
+#![cfg(feature = "serializers")]
+use apache_age::serializers::literal::to_string;
+use apache_age::AgValue;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Serialize)]
+enum Status {
+    Active,
+    Closed { reason: String },
+    Moved(u32),
+    Pair(u8, u8),
+}
+
+#[derive(Serialize)]
+struct Task {
+    pub name: String,
+    pub tags: Vec<&'static str>,
+    pub status: Status,
+    pub description: Option<String>,
+    #[serde(rename = "due date")]
+    pub due_date: (),
+}
+
+#[test]
+fn test_struct() {
+    let task = Task {
+        name: "O'Brien \\ \"quoted\"".into(),
+        tags: vec!["a", "zażółć"],
+        status: Status::Closed {
+            reason: "line\nbreak".into(),
+        },
+        description: None,
+        due_date: (),
+    };
+
+    assert_eq!(
+        to_string(&task).unwrap(),
+        r#"{name: 'O\'Brien \\ "quoted"', tags: ['a', 'zażółć'], status: {Closed: {reason: 'line\nbreak'}}, description: null, `due date`: null}"#
+    );
+}
+
+#[test]
+fn test_enums() {
+    let statuses = [Status::Active, Status::Moved(3), Status::Pair(1, 2)];
+    assert_eq!(
+        to_string(&statuses).unwrap(),
+        "['Active', {Moved: 3}, {Pair: [1, 2]}]"
+    );
+}
+
+#[test]
+fn test_keys() {
+    let map = BTreeMap::from([
+        ("plain_key1", 1),
+        ("with space", 2),
+        ("back`tick", 3),
+        ("1starts_with_digit", 4),
+        ("", 5),
+    ]);
+    assert_eq!(
+        to_string(&map).unwrap(),
+        "{``: 5, `1starts_with_digit`: 4, `back``tick`: 3, plain_key1: 1, `with space`: 2}"
+    );
+
+    let map = BTreeMap::from([(vec![1], 1)]);
+    assert!(to_string(&map).is_err());
+}
+
+#[test]
+fn test_numbers() {
+    assert_eq!(to_string(&-5i8).unwrap(), "-5");
+    assert_eq!(to_string(&i64::MAX).unwrap(), "9223372036854775807");
+    assert_eq!(
+        to_string(&u64::MAX).unwrap(),
+        "18446744073709551615::numeric"
+    );
+    assert_eq!(
+        to_string(&i128::MIN).unwrap(),
+        "-170141183460469231731687303715884105728::numeric"
+    );
+    assert_eq!(to_string(&1.0f64).unwrap(), "1.0");
+    assert_eq!(
+        to_string(&[f64::NAN, f64::INFINITY, f64::NEG_INFINITY]).unwrap(),
+        "['NaN'::float, 'Infinity'::float, '-Infinity'::float]"
+    );
+
+    let value: AgValue = "[0.1000000000000000000001::numeric]".parse().unwrap();
+    let numeric = &value.as_list().unwrap()[0];
+    match numeric {
+        AgValue::Numeric(x) => {
+            assert_eq!(to_string(x).unwrap(), "0.1000000000000000000001::numeric")
+        }
+        x => panic!("Expected numeric, got {:?}", x),
+    }
+}
+
+#[test]
+fn test_control_characters() {
+    assert_eq!(
+        to_string("\u{1}\t\r\u{8}\u{c}").unwrap(),
+        r"'\u0001\t\r\b\f'"
+    );
+}
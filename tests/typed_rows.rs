@@ -0,0 +1,148 @@
+#![cfg(all(feature = "sync", feature = "derive"))]
+#![allow(unused_must_use)]
+
+use apache_age::sync::{AgeClient, Client};
+use apache_age::{AgType, FromAgRow, NoTls, Vertex};
+use rand::{distributions::Alphanumeric, Rng};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct Person {
+    pub name: String,
+    pub surname: String,
+}
+
+#[derive(FromAgRow)]
+struct PersonWithCount {
+    pub person: Vertex<Person>,
+    #[age(rename = "count(*)")]
+    pub count: AgType<i64>,
+}
+
+#[derive(FromAgRow)]
+struct NameAndSurname(AgType<String>, AgType<String>);
+
+struct TestConnection {
+    pub client: Client,
+    pub graph_name: String,
+}
+
+impl TestConnection {
+    pub fn new() -> Self {
+        let (client, graph_name) = connect();
+
+        Self { client, graph_name }
+    }
+}
+
+impl Drop for TestConnection {
+    fn drop(&mut self) {
+        self.client.drop_graph(&self.graph_name);
+    }
+}
+
+fn connect() -> (Client, String) {
+    let mut client = Client::connect_age(
+        "host=localhost user=postgres password=passwd port=8081",
+        NoTls,
+    )
+    .unwrap();
+
+    let graph_name = "age_test_".to_string()
+        + &rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(7)
+            .map(char::from)
+            .collect::<String>();
+
+    assert!(client.create_graph(&graph_name).is_ok());
+
+    (client, graph_name)
+}
+
+#[test]
+fn tuples() {
+    let mut tc = TestConnection::new();
+
+    tc.client.execute_cypher::<()>(
+        &tc.graph_name,
+        "CREATE(n: Person {name: 'John', surname: 'Doe'})",
+        None,
+    );
+
+    let rows: Vec<(Vertex<Person>, AgType<String>)> = tc
+        .client
+        .query_cypher_as::<_, ()>(&tc.graph_name, "MATCH (n: Person) RETURN n, n.name", None)
+        .unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].0.properties().surname, "Doe");
+    assert_eq!(rows[0].1 .0, "John");
+
+    let person: Vertex<Person> = tc
+        .client
+        .query_one_cypher_as::<_, ()>(&tc.graph_name, "MATCH (n: Person) RETURN n", None)
+        .unwrap();
+    assert_eq!(person.properties().name, "John");
+
+    // Wrong type is an error, not a panic
+    assert!(tc
+        .client
+        .query_cypher_as::<(Vertex<Person>, Vertex<Person>), ()>(
+            &tc.graph_name,
+            "MATCH (n: Person) RETURN n, n.name",
+            None,
+        )
+        .is_err());
+}
+
+#[test]
+fn derived() {
+    let mut tc = TestConnection::new();
+
+    tc.client.execute_cypher::<()>(
+        &tc.graph_name,
+        "CREATE(n: Person {name: 'John', surname: 'Doe'})",
+        None,
+    );
+
+    let x: PersonWithCount = tc
+        .client
+        .query_one_cypher_as::<_, ()>(
+            &tc.graph_name,
+            "MATCH (person: Person) RETURN person, count(*)",
+            None,
+        )
+        .unwrap();
+    assert_eq!(x.person.properties().name, "John");
+    assert_eq!(x.count.0, 1);
+
+    let x: Option<NameAndSurname> = tc
+        .client
+        .query_opt_cypher_as::<_, ()>(
+            &tc.graph_name,
+            "MATCH (n: Person) RETURN n.name, n.surname",
+            None,
+        )
+        .unwrap();
+    let x = x.unwrap();
+    assert_eq!((x.0 .0.as_str(), x.1 .0.as_str()), ("John", "Doe"));
+
+    let x: Option<NameAndSurname> = tc
+        .client
+        .query_opt_cypher_as::<_, ()>(
+            &tc.graph_name,
+            "MATCH (n: Person) WHERE n.name = 'Jane' RETURN n.name, n.surname",
+            None,
+        )
+        .unwrap();
+    assert!(x.is_none());
+
+    assert!(tc
+        .client
+        .query_one_cypher_as::<NameAndSurname, ()>(
+            &tc.graph_name,
+            "MATCH (n: Person) WHERE n.name = 'Jane' RETURN n.name, n.surname",
+            None,
+        )
+        .is_err());
+}